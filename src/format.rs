@@ -0,0 +1,33 @@
+//! Human-readable balances for debug output.
+
+use sp_std::fmt;
+
+/// A balance displayed with its asset's decimals, e.g. `12345` with 2 decimals as `123.45`.
+///
+/// Only integer math is used so it formats the same in `no_std` builds.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct FormattedBalance {
+    pub amount: u128,
+    pub decimals: u8,
+}
+
+impl fmt::Display for FormattedBalance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.decimals as usize;
+        if width == 0 {
+            return write!(f, "{}", self.amount);
+        }
+
+        match 10u128.checked_pow(self.decimals.into()) {
+            Some(unit) => write!(f, "{}.{:0width$}", self.amount / unit, self.amount % unit, width = width),
+            // More decimals than `u128` has digits: the whole amount is fractional.
+            None => write!(f, "0.{:0width$}", self.amount, width = width),
+        }
+    }
+}
+
+impl fmt::Debug for FormattedBalance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -1,13 +1,31 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, Parameter};
-use frame_system::ensure_signed;
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    traits::{Currency, ExistenceRequirement, Get},
+    weights::Weight,
+    IterableStorageDoubleMap, IterableStorageMap, Parameter, StoragePrefixedMap,
+};
+use frame_system::{ensure_root, ensure_signed};
 use codec::{Decode, Encode};
-use sp_runtime::{DispatchResult, RuntimeDebug};
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational,
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+    DispatchError, DispatchResult, RuntimeDebug,
+};
 use sp_runtime::traits::{
-    AtLeast32Bit, AtLeast32BitUnsigned, CheckedSub, MaybeSerializeDeserialize, Member, One, Saturating, StaticLookup,
-    Zero,
+    AtLeast32Bit, AtLeast32BitUnsigned, Bounded, CheckedMul, CheckedSub, Hash as HashT, IdentifyAccount,
+    MaybeSerializeDeserialize, Member, One, SaturatedConversion, Saturating, StaticLookup, Verify, Zero,
 };
+use sp_std::{
+    convert::{TryFrom, TryInto},
+    prelude::*,
+};
+
+mod format;
+pub mod runtime_api;
+
+pub use format::FormattedBalance;
 
 #[cfg(test)]
 mod mock;
@@ -17,6 +35,24 @@ mod tests;
 type Symbol = [u8; 8];
 type Name = [u8; 16];
 
+/// Denominator of amounts expressed in basis points.
+const MAX_BPS: u16 = 10_000;
+
+/// Prefix of the message signed for a permit, so transfer signatures can't pass as permits.
+const PERMIT_CONTEXT: [u8; 6] = *b"permit";
+
+/// Scaling factor of an asset that hasn't been rebased, i.e. one.
+const SCALING_ACCURACY: u128 = 1_000_000_000_000_000_000;
+
+/// Number of blocks with transfers whose volume is kept per asset in `BlockVolumes`.
+const MAX_VOLUME_BLOCKS: usize = 32;
+
+/// Longest alias an account can register.
+const MAX_ALIAS_LENGTH: usize = 32;
+
+/// Number of transfers kept per asset in `RecentTransfers`.
+const MAX_RECENT_TRANSFERS: usize = 16;
+
 #[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, Default)]
 pub struct AssetInfo {
     pub name: Name,
@@ -24,12 +60,177 @@ pub struct AssetInfo {
     pub decimals: u8,
 }
 
+/// Every per-asset setting, for rendering an asset's full profile in one read.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct AssetConfig<AccountId, TokenBalance, BlockNumber> {
+    pub owner: AccountId,
+    pub fee_bps: u16,
+    pub fee_collector: AccountId,
+    pub min_hold_blocks: BlockNumber,
+    pub aggregate_transfer_events: bool,
+    pub allow_delegation: bool,
+    pub restrict_destinations: bool,
+    pub max_supply: Option<TokenBalance>,
+    pub mint_frozen: bool,
+    pub min_balance: TokenBalance,
+    pub min_precision: Option<u8>,
+    pub asset_frozen: bool,
+    pub freeze_renounced: bool,
+    /// Decimals the issuer prefers balances to be shown with; accounting uses `decimals`.
+    pub display_decimals: Option<u8>,
+    pub wound_down: bool,
+    pub event_verbosity: EventVerbosity,
+    pub enumerable: bool,
+    pub trading_start_block: BlockNumber,
+    pub max_allowance: Option<TokenBalance>,
+    /// Amount above which transfers must be confirmed, and the blocks allowed for confirming.
+    pub large_transfer_confirmation: Option<(TokenBalance, BlockNumber)>,
+    pub wash_trade_protection: bool,
+}
+
+/// How much of a transfer an asset's `Transferred` events carry.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, RuntimeDebug)]
+pub enum EventVerbosity {
+    /// `Transferred` with sender, recipient and amount.
+    Full,
+    /// `TransferredAmount` with the amount only.
+    MinimalAmountOnly,
+    /// No event per transfer.
+    None,
+}
+
+impl Default for EventVerbosity {
+    fn default() -> Self {
+        EventVerbosity::Full
+    }
+}
+
+/// Limits enforced by the pallet, for clients to validate calls before submitting them.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct PalletLimits {
+    /// Most entries a batch call accepts.
+    pub max_batch_size: u32,
+    /// Most transfers kept per asset by `recent_transfers`.
+    pub max_recent_transfers: u32,
+    /// Highest transfer fee, in basis points.
+    pub max_fee_bps: u16,
+}
+
+/// An allowance that is topped back up to `replenish_to` once every `period` blocks.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct StandingApproval<TokenBalance, BlockNumber> {
+    pub replenish_to: TokenBalance,
+    pub period: BlockNumber,
+    pub last_replenish: BlockNumber,
+}
+
+/// `locked` tokens released linearly at `per_block` from `starting_block` on.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct VestingSchedule<TokenBalance, BlockNumber> {
+    pub locked: TokenBalance,
+    pub per_block: TokenBalance,
+    pub starting_block: BlockNumber,
+}
+
+/// A pro-rata distribution of `total_reward` in `reward_id` among an asset's holders, paid out
+/// in chunks. Holders' balances are first snapshotted in chunks; `snapshot_cursor` is the raw
+/// storage key of the last balance visited, or `None` once the snapshot is complete.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct Distribution<AssetId, TokenBalance> {
+    pub reward_id: AssetId,
+    pub total_reward: TokenBalance,
+    pub supply: TokenBalance,
+    pub paid: TokenBalance,
+    pub snapshot_cursor: Option<Vec<u8>>,
+}
+
+/// Tokens taken from a sender by `initiate_transfer`, sent to `target` when confirmed by
+/// `deadline` and returned otherwise.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct PendingTransfer<AccountId, TokenBalance, BlockNumber> {
+    pub target: AccountId,
+    pub amount: TokenBalance,
+    pub deadline: BlockNumber,
+}
+
+/// Hook run once an asset has been destroyed, e.g. to deregister it from other modules.
+pub trait OnAssetDestroyed<AssetId> {
+    fn on_destroyed(id: &AssetId);
+}
+
+impl<AssetId> OnAssetDestroyed<AssetId> for () {
+    fn on_destroyed(_id: &AssetId) {}
+}
+
+/// Conversion between token amounts of an asset and native currency amounts. Both directions
+/// return `None` where the amount can't be represented.
+pub trait BalanceConverter<AssetId, TokenBalance, NativeBalance> {
+    fn to_native(id: &AssetId, amount: TokenBalance) -> Option<NativeBalance>;
+    fn from_native(id: &AssetId, amount: NativeBalance) -> Option<TokenBalance>;
+}
+
+/// One token unit is one native unit, for every asset.
+impl<AssetId, TokenBalance, NativeBalance> BalanceConverter<AssetId, TokenBalance, NativeBalance> for ()
+where
+    TokenBalance: AtLeast32BitUnsigned,
+    NativeBalance: AtLeast32BitUnsigned,
+{
+    fn to_native(_id: &AssetId, amount: TokenBalance) -> Option<NativeBalance> {
+        let amount: u128 = amount.try_into().ok()?;
+        <NativeBalance as TryFrom<u128>>::try_from(amount).ok()
+    }
+
+    fn from_native(_id: &AssetId, amount: NativeBalance) -> Option<TokenBalance> {
+        let amount: u128 = amount.try_into().ok()?;
+        <TokenBalance as TryFrom<u128>>::try_from(amount).ok()
+    }
+}
+
+/// Weights of the pallet's calls, as benchmarked for the runtime.
+pub trait WeightInfo {
+    fn transfer() -> Weight;
+}
+
+/// Unbenchmarked: every call weighs nothing.
+impl WeightInfo for () {
+    fn transfer() -> Weight {
+        0
+    }
+}
+
 pub trait Trait: frame_system::Trait {
     type TokenBalance: Member + Parameter + AtLeast32BitUnsigned + Default + Copy + MaybeSerializeDeserialize;
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
     type AssetId: Parameter + AtLeast32Bit + Default + Copy + MaybeSerializeDeserialize;
+    /// Null account; transfers to it burn the amount instead of crediting it.
+    type BurnAddress: Get<Self::AccountId>;
+    /// Maximum number of entries accepted by batch calls.
+    type MaxBatchSize: Get<u32>;
+    /// Signature authorising a transfer relayed on behalf of its signer.
+    type Signature: Parameter + Verify<Signer = Self::Signer>;
+    type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+    /// Whether to record which accounts have sent each asset to each other. Costs a storage
+    /// entry per distinct sender/recipient pair.
+    type TrackCounterparties: Get<bool>;
+    /// Native currency paid out when redeeming tokens.
+    type Currency: Currency<Self::AccountId>;
+    /// Called for each destroyed asset; `()` does nothing.
+    type OnAssetDestroyed: OnAssetDestroyed<Self::AssetId>;
+    /// Converts token amounts to and from `Currency` amounts for redemptions, `pay` and fees
+    /// paid in the fee asset; `()` converts one to one.
+    type BalanceConverter: BalanceConverter<
+        Self::AssetId,
+        Self::TokenBalance,
+        <Self::Currency as Currency<Self::AccountId>>::Balance,
+    >;
+    /// Blocks that must pass between committing to a transfer and revealing it.
+    type RevealDelay: Get<Self::BlockNumber>;
+    /// Benchmarked weights of the transfer calls, also used by `estimate_batch_weight`.
+    type WeightInfo: WeightInfo;
 }
 
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
 decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
         type Error = Error<T>;
@@ -42,6 +243,56 @@ decl_module! {
             Self::inner_issue(&origin, total, &asset_info);
         }
 
+        /// Issue an asset whose supply can never grow past `total`.
+        #[weight = 0]
+        fn issue_fixed(origin, #[compact] total: T::TokenBalance, asset_info: AssetInfo) {
+            let origin = ensure_signed(origin)?;
+
+            let id = Self::inner_issue(&origin, total, &asset_info);
+            Self::deposit_event(RawEvent::MetadataSet(id, asset_info));
+            Self::inner_freeze_minting(&id);
+        }
+
+        /// Issue an asset minted straight to the `distribution` recipients, with its transfer fee,
+        /// fee collector and supply cap already set.
+        #[weight = T::WeightInfo::transfer().saturating_mul(distribution.len() as Weight)]
+        fn launch_asset(origin,
+            asset_info: AssetInfo,
+            distribution: Vec<(<T::Lookup as StaticLookup>::Source, T::TokenBalance)>,
+            fee_bps: u16,
+            collector: Option<<T::Lookup as StaticLookup>::Source>,
+            max_supply: Option<T::TokenBalance>
+        ) {
+            let origin = ensure_signed(origin)?;
+            ensure!(distribution.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+            let distribution = distribution
+                .into_iter()
+                .map(|(to, amount)| Ok((T::Lookup::lookup(to)?, amount)))
+                .collect::<Result<Vec<_>, DispatchError>>()?;
+            let collector = collector.map(T::Lookup::lookup).transpose()?;
+
+            Self::inner_launch_asset(
+                &origin,
+                &asset_info,
+                distribution,
+                fee_bps,
+                collector,
+                max_supply,
+            )?;
+        }
+
+        /// Replace per-transfer `Transferred` events of an asset with one `TransfersAggregated`
+        /// event per block, emitted in `on_finalize`.
+        #[weight = 0]
+        fn set_aggregate_transfer_events(origin, #[compact] id: T::AssetId, enabled: bool) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <AggregateTransferEvents<T>>::insert(id, enabled);
+
+            Self::deposit_event(RawEvent::AggregateTransferEventsSet(id, enabled));
+        }
+
         #[weight = 0]
         fn approve(origin,
             #[compact] id: T::AssetId,
@@ -53,8 +304,37 @@ decl_module! {
 
             Self::inner_approve(&id, &owner, &spender, amount)?;
         }
-        
+
+        /// Approve `spender` for `replenish_to`, restoring the allowance to that amount once
+        /// every `period` blocks.
+        #[weight = 0]
+        fn set_standing_approval(origin,
+            #[compact] id: T::AssetId,
+            spender: <T::Lookup as StaticLookup>::Source,
+            #[compact] replenish_to: T::TokenBalance,
+            period: T::BlockNumber
+        ) {
+            let owner = ensure_signed(origin)?;
+            let spender = T::Lookup::lookup(spender)?;
+
+            Self::inner_set_standing_approval(&id, &owner, &spender, replenish_to, period)?;
+        }
+
+        /// Move the caller's entire allowance from `old_spender` to `new_spender`.
         #[weight = 0]
+        fn reassign_allowance(origin,
+            #[compact] id: T::AssetId,
+            old_spender: <T::Lookup as StaticLookup>::Source,
+            new_spender: <T::Lookup as StaticLookup>::Source
+        ) {
+            let owner = ensure_signed(origin)?;
+            let old_spender = T::Lookup::lookup(old_spender)?;
+            let new_spender = T::Lookup::lookup(new_spender)?;
+
+            Self::inner_reassign_allowance(&id, &owner, &old_spender, &new_spender)?;
+        }
+        
+        #[weight = T::WeightInfo::transfer()]
         fn transfer(origin,
             #[compact] id: T::AssetId,
             target: <T::Lookup as StaticLookup>::Source,
@@ -66,6 +346,51 @@ decl_module! {
             Self::inner_transfer(&id, &origin, &target, amount)?;
         }
 
+        /// Like `transfer`, but fails if `target` would end up below the asset's minimum balance.
+        #[weight = 0]
+        fn transfer_no_dust(origin,
+            #[compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::TokenBalance
+        ) {
+            let origin = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            Self::inner_transfer_no_dust(&id, &origin, &target, amount)?;
+        }
+
+        /// Transfer everything above `floor` to `target`, leaving exactly `floor` behind.
+        #[weight = 0]
+        fn transfer_above_floor(origin,
+            #[compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[compact] floor: T::TokenBalance
+        ) {
+            let origin = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            let balance = Self::balance_of(&id, &origin);
+            ensure!(balance > floor, Error::<T>::BalanceTooLow);
+
+            Self::inner_transfer(&id, &origin, &target, balance - floor)?;
+        }
+
+        /// Transfer `amount` to `target` and approve the caller to take up to `amount` back from it.
+        #[weight = 0]
+        fn transfer_with_return_approval(origin,
+            #[compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::TokenBalance
+        ) {
+            let origin = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+            ensure!(Self::allow_delegation(&id), Error::<T>::DelegationDisabled);
+            Self::ensure_allowance_within_cap(&id, amount)?;
+
+            Self::inner_transfer(&id, &origin, &target, amount)?;
+            Self::inner_approve(&id, &target, &origin, amount)?;
+        }
+
         #[weight = 0]
         fn transfer_from(origin,
             #[compact] id: T::AssetId,
@@ -79,165 +404,3030 @@ decl_module! {
 
             Self::inner_transfer_from(&id, &owner, &spender, &target, amount)?;
         }
-    }
-}
 
-decl_event! {
-    pub enum Event<T> where
-        <T as frame_system::Trait>::AccountId,
-        <T as Trait>::TokenBalance,
-        <T as Trait>::AssetId,
-    {
-        Issued(AssetId, AccountId, TokenBalance),
-        Transferred(AssetId, AccountId, AccountId, TokenBalance),
-        Approval(AssetId, AccountId, AccountId, TokenBalance),
+        /// Allow or block outgoing transfers of `who` for an asset.
+        #[weight = 0]
+        fn set_transfer_gate(origin,
+            #[compact] id: T::AssetId,
+            who: <T::Lookup as StaticLookup>::Source,
+            enabled: bool
+        ) {
+            let origin = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_owner(&id, &origin)?;
 
-        Minted(AssetId, AccountId, TokenBalance),
-        Burned(AssetId, AccountId, TokenBalance),
-    }
-}
+            Self::inner_set_transfer_gate(&id, &who, enabled);
+        }
 
-decl_error! {
-    pub enum Error for Module<T: Trait> {
-        BalanceLow,
-        BalanceZero,
-        AllowanceLow,
-        AmountZero,
-        AssetNotExists,
-    }
-}
+        /// Require received tokens to be held for `blocks` blocks before they can be sent on.
+        #[weight = 0]
+        fn set_min_hold_blocks(origin, #[compact] id: T::AssetId, blocks: T::BlockNumber) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
 
-decl_storage! {
-    trait Store for Module<T: Trait> as Assets {
-        TotalSupply: map hasher(twox_64_concat) T::AssetId => T::TokenBalance;
-        AssetInfos: map hasher(twox_64_concat) T::AssetId => Option<AssetInfo>;
-        Balances: map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => T::TokenBalance;
-        NextAssetId get(fn next_asset_id): T::AssetId;
-        Allowances: map hasher(blake2_128_concat) (T::AssetId, T::AccountId, T::AccountId) => T::TokenBalance;
-    }
-}
+            <MinHoldBlocks<T>>::insert(id, blocks);
 
-impl<T: Trait> Module<T> {
-    pub fn total_supply(id: &T::AssetId) -> T::TokenBalance {
-        <TotalSupply<T>>::get(id)
-    }
+            Self::deposit_event(RawEvent::MinHoldBlocksSet(id, blocks));
+        }
 
-    pub fn balance_of(id: &T::AssetId, owner: &T::AccountId) -> T::TokenBalance {
-        <Balances<T>>::get((id, owner))
-    }
+        /// Charge `fee_bps` basis points of every transfer to the asset's fee collector.
+        #[weight = 0]
+        fn set_transfer_fee(origin, #[compact] id: T::AssetId, fee_bps: u16) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+            ensure!(fee_bps <= MAX_BPS, Error::<T>::InvalidFee);
 
-    pub fn inner_issue(
-        owner: &T::AccountId,
-        initial_supply: T::TokenBalance,
-        info: &AssetInfo,
-    ) -> T::AssetId {
-        let id = Self::next_asset_id();
-        <NextAssetId<T>>::mutate(|id| *id += One::one());
+            <TransferFees<T>>::insert(id, fee_bps);
 
-        <Balances<T>>::insert((id, owner), initial_supply);
-        <TotalSupply<T>>::insert(id, initial_supply);
-        <AssetInfos<T>>::insert(id, info);
+            Self::deposit_event(RawEvent::TransferFeeSet(id, fee_bps));
+        }
 
-        Self::deposit_event(RawEvent::Issued(id, owner.clone(), initial_supply));
+        /// Route the asset's transfer fees to `who` instead of the owner.
+        #[weight = 0]
+        fn set_fee_collector(origin,
+            #[compact] id: T::AssetId,
+            who: <T::Lookup as StaticLookup>::Source
+        ) {
+            let origin = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_owner(&id, &origin)?;
 
-        id
-    }
+            <FeeCollectors<T>>::insert(id, &who);
 
-    pub fn asset_info(id: &T::AssetId) -> Option<AssetInfo> {
-        <AssetInfos<T>>::get(id)
-    }
+            Self::deposit_event(RawEvent::FeeCollectorSet(id, who));
+        }
 
-    pub fn inner_transfer(
-        id: &T::AssetId,
-        owner: &T::AccountId,
-        target: &T::AccountId,
-        amount: T::TokenBalance,
-    ) -> DispatchResult {
-        let owner_balance = <Balances<T>>::get((id, owner));
-        ensure!(!amount.is_zero(), Error::<T>::AmountZero);
-        ensure!(owner_balance >= amount, Error::<T>::BalanceLow);
+        /// Block outgoing transfers of `who`.
+        #[weight = 0]
+        fn freeze(origin, #[compact] id: T::AssetId, who: <T::Lookup as StaticLookup>::Source) {
+            let origin = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_freezer(&id, &origin)?;
 
-        let new_balance = owner_balance.saturating_sub(amount);
+            Self::inner_freeze(&id, &who);
+        }
 
-        <Balances<T>>::mutate((id, owner), |balance| *balance = new_balance);
-        <Balances<T>>::mutate((id, target), |balance| {
-            *balance = balance.saturating_add(amount)
-        });
+        /// Freeze every account in `accounts`.
+        #[weight = 0]
+        fn freeze_many(origin,
+            #[compact] id: T::AssetId,
+            accounts: Vec<<T::Lookup as StaticLookup>::Source>
+        ) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_freezer(&id, &origin)?;
+            ensure!(accounts.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
 
-        Self::deposit_event(RawEvent::Transferred(
-            *id,
-            owner.clone(),
-            target.clone(),
-            amount,
-        ));
+            let accounts = accounts
+                .into_iter()
+                .map(T::Lookup::lookup)
+                .collect::<Result<Vec<_>, _>>()?;
+            for who in accounts.iter() {
+                Self::inner_freeze(&id, who);
+            }
+        }
 
-        Ok(())
-    }
+        /// Unblock outgoing transfers of `who`.
+        #[weight = 0]
+        fn thaw(origin, #[compact] id: T::AssetId, who: <T::Lookup as StaticLookup>::Source) {
+            let origin = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_freezer(&id, &origin)?;
 
-    pub fn inner_transfer_from(
-        id: &T::AssetId,
-        owner: &T::AccountId,
-        spender: &T::AccountId,
-        target: &T::AccountId,
-        amount: T::TokenBalance,
-    ) -> DispatchResult {
-        let allowance = <Allowances<T>>::get((id, owner, spender));
-        let new_balance = allowance
-            .checked_sub(&amount)
-            .ok_or(Error::<T>::AllowanceLow)?;
+            <Frozen<T>>::remove((id, &who));
 
-        Self::inner_transfer(&id, &owner, &target, amount)?;
+            Self::deposit_event(RawEvent::Thawed(id, who));
+        }
 
-        <Allowances<T>>::mutate((id, owner, spender), |balance| *balance = new_balance);
+        /// Block all transfers of an asset.
+        #[weight = 0]
+        fn freeze_asset(origin, #[compact] id: T::AssetId) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_freezer(&id, &origin)?;
 
-        Ok(())
-    }
+            <AssetFrozen<T>>::insert(id, true);
 
-    pub fn inner_approve(
-        id: &T::AssetId,
-        owner: &T::AccountId,
-        spender: &T::AccountId,
-        amount: T::TokenBalance,
-    ) -> DispatchResult {
-        <Allowances<T>>::mutate((id, owner, spender), |balance| *balance = amount);
+            Self::deposit_event(RawEvent::AssetFrozen(id));
+        }
 
-        Self::deposit_event(RawEvent::Approval(
-            *id,
-            owner.clone(),
-            spender.clone(),
-            amount,
-        ));
+        #[weight = 0]
+        fn thaw_asset(origin, #[compact] id: T::AssetId) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_freezer(&id, &origin)?;
 
-        Ok(())
-    }
+            <AssetFrozen<T>>::remove(id);
 
-    pub fn allowances(id: &T::AssetId, owner: &T::AccountId, spender: &T::AccountId) -> T::TokenBalance {
-        <Allowances<T>>::get((id, owner, spender))
-    }
+            Self::deposit_event(RawEvent::AssetThawed(id));
+        }
 
-    pub fn inner_mint(id: &T::AssetId, owner: &T::AccountId, amount: T::TokenBalance) -> DispatchResult {
-        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+        /// Irreversibly give up the ability to freeze or thaw accounts and the asset.
+        ///
+        /// Accounts frozen at this point stay frozen for good; the asset itself must not be frozen.
+        #[weight = 0]
+        fn renounce_freeze_authority(origin, #[compact] id: T::AssetId) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_freezer(&id, &origin)?;
+            ensure!(!Self::is_asset_frozen(&id), Error::<T>::AssetFrozen);
 
-        let new_balance = <Balances<T>>::get((id, owner)).saturating_add(amount);
+            <FreezeRenounced<T>>::insert(id, true);
 
-        <Balances<T>>::mutate((id, owner), |balance| *balance = new_balance);
-        <TotalSupply<T>>::mutate(id, |supply| {
-            *supply = supply.saturating_add(amount);
-        });
+            Self::deposit_event(RawEvent::FreezeAuthorityRenounced(id));
+        }
 
-        Self::deposit_event(RawEvent::Minted(*id, owner.clone(), amount));
+        /// Enable or disable approvals and `transfer_from` for an asset.
+        #[weight = 0]
+        fn set_allow_delegation(origin, #[compact] id: T::AssetId, allowed: bool) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
 
-        Ok(())
-    }
+            <AllowDelegation<T>>::insert(id, allowed);
 
-    pub fn inner_burn(id: &T::AssetId, owner: &T::AccountId, amount: T::TokenBalance) -> DispatchResult {
+            Self::deposit_event(RawEvent::AllowDelegationSet(id, allowed));
+        }
+
+        /// Zero the allowance `owner` granted to `spender`, without the owner's consent.
+        #[weight = 0]
+        fn force_revoke_allowance(origin,
+            #[compact] id: T::AssetId,
+            owner: <T::Lookup as StaticLookup>::Source,
+            spender: <T::Lookup as StaticLookup>::Source
+        ) {
+            Self::ensure_root_or_owner(origin, &id)?;
+            let owner = T::Lookup::lookup(owner)?;
+            let spender = T::Lookup::lookup(spender)?;
+
+            Self::inner_approve(&id, &owner, &spender, Zero::zero())?;
+        }
+
+        /// Set allowances directly from a snapshot, e.g. when migrating an asset from another
+        /// chain. Root only, as it bypasses the delegation and allowance cap settings.
+        #[weight = 0]
+        fn owner_preauthorize(origin,
+            #[compact] id: T::AssetId,
+            grants: Vec<(T::AccountId, T::AccountId, T::TokenBalance)>
+        ) {
+            ensure_root(origin)?;
+            ensure!(Self::asset_info(&id).is_some(), Error::<T>::AssetNotExists);
+            ensure!(grants.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+
+            for (owner, spender, amount) in grants {
+                Self::set_allowance(&id, &owner, &spender, amount);
+                <StandingApprovals<T>>::remove((id, &owner, &spender));
+
+                Self::deposit_event(RawEvent::Approval(id, owner, spender, amount));
+            }
+        }
+
+        /// Relay a transfer from `from`, authorised by its signature over
+        /// `(id, target, amount, nonce)`.
+        #[weight = 0]
+        fn transfer_signed(origin,
+            #[compact] id: T::AssetId,
+            from: <T::Lookup as StaticLookup>::Source,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::TokenBalance,
+            nonce: u32,
+            signature: T::Signature
+        ) {
+            ensure_signed(origin)?;
+            let from = T::Lookup::lookup(from)?;
+            let target = T::Lookup::lookup(target)?;
+
+            Self::inner_transfer_signed(&id, &from, &target, amount, nonce, &signature)?;
+        }
+
+        /// Set approvals authorised by each owner's signature over
+        /// `(b"permit", id, spender, amount, nonce)`. If any permit is invalid, none is applied.
+        #[weight = 0]
+        fn batch_permit(origin,
+            #[compact] id: T::AssetId,
+            permits: Vec<(T::AccountId, T::AccountId, T::TokenBalance, u32, T::Signature)>
+        ) {
+            ensure_signed(origin)?;
+            ensure!(permits.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+
+            Self::inner_batch_permit(&id, &permits)?;
+        }
+
+        /// Only allow transfers to destinations approved with `set_allowed_destination`.
+        #[weight = 0]
+        fn set_restrict_destinations(origin, #[compact] id: T::AssetId, enabled: bool) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <RestrictDestinations<T>>::insert(id, enabled);
+
+            Self::deposit_event(RawEvent::RestrictDestinationsSet(id, enabled));
+        }
+
+        #[weight = 0]
+        fn set_allowed_destination(origin,
+            #[compact] id: T::AssetId,
+            who: <T::Lookup as StaticLookup>::Source,
+            allowed: bool
+        ) {
+            let origin = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            if allowed {
+                <AllowedDestinations<T>>::insert((id, &who), true);
+            } else {
+                <AllowedDestinations<T>>::remove((id, &who));
+            }
+
+            Self::deposit_event(RawEvent::AllowedDestinationSet(id, who, allowed));
+        }
+
+        /// Block transfers of an asset from an account to itself or to an account marked related
+        /// to it by `set_related_accounts`. A best-effort guard against wash trading.
+        #[weight = 0]
+        fn set_wash_trade_protection(origin, #[compact] id: T::AssetId, enabled: bool) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <WashTradeProtection<T>>::insert(id, enabled);
+
+            Self::deposit_event(RawEvent::WashTradeProtectionSet(id, enabled));
+        }
+
+        /// Mark `a` and `b` as controlled by the same party, or no longer so.
+        #[weight = 0]
+        fn set_related_accounts(origin,
+            #[compact] id: T::AssetId,
+            a: <T::Lookup as StaticLookup>::Source,
+            b: <T::Lookup as StaticLookup>::Source,
+            related: bool
+        ) {
+            let origin = ensure_signed(origin)?;
+            let a = T::Lookup::lookup(a)?;
+            let b = T::Lookup::lookup(b)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            if related {
+                <RelatedAccounts<T>>::insert((id, &a, &b), true);
+                <RelatedAccounts<T>>::insert((id, &b, &a), true);
+            } else {
+                <RelatedAccounts<T>>::remove((id, &a, &b));
+                <RelatedAccounts<T>>::remove((id, &b, &a));
+            }
+
+            Self::deposit_event(RawEvent::RelatedAccountsSet(id, a, b, related));
+        }
+
+        /// Cap the total supply of an asset, or remove the cap with `None`.
+        #[weight = 0]
+        fn set_max_supply(origin, #[compact] id: T::AssetId, max_supply: Option<T::TokenBalance>) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+            if let Some(max) = max_supply {
+                ensure!(Self::total_supply(&id) <= max, Error::<T>::SupplyCapExceeded);
+            }
+
+            <MaxSupply<T>>::mutate(id, |max| *max = max_supply);
+
+            Self::deposit_event(RawEvent::MaxSupplySet(id, max_supply));
+        }
+
+        /// Reject transfers of an asset before block `start`. Minting is unaffected, so the owner
+        /// can distribute the asset before trading opens.
+        #[weight = 0]
+        fn set_trading_start_block(origin, #[compact] id: T::AssetId, start: T::BlockNumber) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <TradingStartBlock<T>>::insert(id, start);
+
+            Self::deposit_event(RawEvent::TradingStartBlockSet(id, start));
+        }
+
+        /// Whether the holder listing runtime APIs return an asset's holders. Only hides them from
+        /// those APIs; balances stay public in storage.
+        #[weight = 0]
+        fn set_enumerable(origin, #[compact] id: T::AssetId, enumerable: bool) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <Enumerable<T>>::insert(id, enumerable);
+
+            Self::deposit_event(RawEvent::EnumerableSet(id, enumerable));
+        }
+
+        /// Set how much data the transfer events of an asset carry.
+        #[weight = 0]
+        fn set_event_verbosity(origin, #[compact] id: T::AssetId, verbosity: EventVerbosity) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <TransferEventVerbosity<T>>::insert(id, verbosity);
+
+            Self::deposit_event(RawEvent::EventVerbositySet(id, verbosity));
+        }
+
+        /// Permanently stop transfers and mints of an asset, leaving holders only able to burn.
+        #[weight = 0]
+        fn wind_down(origin, #[compact] id: T::AssetId) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <WoundDown<T>>::insert(id, true);
+
+            Self::deposit_event(RawEvent::AssetWoundDown(id));
+        }
+
+        /// Cap the amount of any single approval of an asset, or remove the cap with `None`.
+        #[weight = 0]
+        fn set_max_allowance(origin, #[compact] id: T::AssetId, max_allowance: Option<T::TokenBalance>) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <MaxAllowance<T>>::mutate(id, |max| *max = max_allowance);
+
+            Self::deposit_event(RawEvent::MaxAllowanceSet(id, max_allowance));
+        }
+
+        /// Permanently disable minting of an asset.
+        #[weight = 0]
+        fn freeze_minting(origin, #[compact] id: T::AssetId) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            Self::inner_freeze_minting(&id);
+        }
+
+        /// Change the asset's minimum balance.
+        ///
+        /// Existing balances are never touched. Raising the minimum is allowed even though some
+        /// holders may now be below it, since finding them would mean iterating every balance;
+        /// `MinBalanceRaised` is emitted as a warning so indexers can flag them instead.
+        #[weight = 0]
+        fn set_min_balance(origin, #[compact] id: T::AssetId, #[compact] new_min: T::TokenBalance) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            let old_min = <MinBalance<T>>::mutate(id, |min| sp_std::mem::replace(min, new_min));
+
+            if new_min > old_min {
+                Self::deposit_event(RawEvent::MinBalanceRaised(id, old_min, new_min));
+            }
+            Self::deposit_event(RawEvent::MinBalanceSet(id, new_min));
+        }
+
+        /// Allow `who` to mint the asset.
+        #[weight = 0]
+        fn add_minter(origin, #[compact] id: T::AssetId, who: <T::Lookup as StaticLookup>::Source) {
+            let origin = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <Minters<T>>::insert((id, &who), true);
+
+            Self::deposit_event(RawEvent::MinterAdded(id, who));
+        }
+
+        #[weight = 0]
+        fn remove_minter(origin, #[compact] id: T::AssetId, who: <T::Lookup as StaticLookup>::Source) {
+            let origin = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <Minters<T>>::remove((id, &who));
+
+            Self::deposit_event(RawEvent::MinterRemoved(id, who));
+        }
+
+        /// Mint `schedule.locked` to `target`, locked under the vesting schedule. Replaces a
+        /// schedule `target` has fully vested.
+        #[weight = 0]
+        fn mint_vested(origin,
+            #[compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+            schedule: VestingSchedule<T::TokenBalance, T::BlockNumber>
+        ) {
+            let origin = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+            Self::ensure_minter(&id, &origin)?;
+
+            Self::inner_mint_vested(&id, &target, schedule)?;
+        }
+
+        /// Set the number of decimals frontends should show balances with, or `None` for the
+        /// asset's decimals. Doesn't change how amounts are interpreted.
+        #[weight = 0]
+        fn set_display_decimals(origin, #[compact] id: T::AssetId, display_decimals: Option<u8>) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            <DisplayDecimals<T>>::mutate(id, |decimals| *decimals = display_decimals);
+
+            Self::deposit_event(RawEvent::DisplayDecimalsSet(id, display_decimals));
+        }
+
+        /// Only allow transfers in multiples of `10^(decimals - min_precision)`.
+        #[weight = 0]
+        fn set_min_precision(origin, #[compact] id: T::AssetId, min_precision: Option<u8>) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+            if let (Some(precision), Some(info)) = (min_precision, Self::asset_info(&id)) {
+                ensure!(precision <= info.decimals, Error::<T>::InvalidPrecision);
+            }
+
+            <MinPrecision<T>>::mutate(id, |precision| *precision = min_precision);
+
+            Self::deposit_event(RawEvent::MinPrecisionSet(id, min_precision));
+        }
+
+        /// Let holders redeem the asset for `rate` native units per token unit, paid from the
+        /// caller's own native balance. A zero rate disables redemption.
+        #[weight = 0]
+        fn set_redemption(origin, #[compact] id: T::AssetId, #[compact] rate: BalanceOf<T>) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            if rate.is_zero() {
+                <Redemptions<T>>::remove(id);
+            } else {
+                <Redemptions<T>>::insert(id, (rate, &origin));
+            }
+
+            Self::deposit_event(RawEvent::RedemptionSet(id, rate, origin));
+        }
+
+        /// Burn `amount` of the caller's tokens for native currency at the redemption rate.
+        #[weight = 0]
+        fn redeem(origin, #[compact] id: T::AssetId, #[compact] amount: T::TokenBalance) {
+            let origin = ensure_signed(origin)?;
+
+            Self::inner_redeem(&id, &origin, amount)?;
+        }
+
+        /// Accept transaction fees in asset `id`, charging `rate` token units per native unit.
+        /// A zero rate stops fees being paid in `id`, leaving any other fee asset in place.
+        #[weight = 0]
+        fn set_fee_asset(origin, #[compact] id: T::AssetId, #[compact] rate: T::TokenBalance) {
+            ensure_root(origin)?;
+            ensure!(Self::asset_info(&id).is_some(), Error::<T>::AssetNotExists);
+
+            if rate.is_zero() {
+                if Self::fee_asset().map(|(fee_id, _)| fee_id) == Some(id) {
+                    FeeAsset::<T>::kill();
+                }
+            } else {
+                FeeAsset::<T>::put((id, rate));
+            }
+
+            Self::deposit_event(RawEvent::FeeAssetSet(id, rate));
+        }
+
+        /// Pay holders of `id` their share of `total_reward` in `reward_id`, taken from the
+        /// caller's balance, snapshotting or paying up to `max` balance entries per call. Call
+        /// again with the same arguments to continue; the rounding remainder goes back to the
+        /// owner once all are paid.
+        #[weight = T::DbWeight::get().reads_writes(
+            (*max as Weight).saturating_mul(3).saturating_add(3),
+            (*max as Weight).saturating_mul(3).saturating_add(3),
+        )]
+        fn distribute(origin,
+            #[compact] id: T::AssetId,
+            #[compact] reward_id: T::AssetId,
+            #[compact] total_reward: T::TokenBalance,
+            max: u32
+        ) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+            ensure!(!max.is_zero(), Error::<T>::AmountZero);
+            ensure!(max <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+
+            Self::inner_distribute(&id, &origin, &reward_id, total_reward, max)?;
+        }
+
+        /// Require transfers above `threshold` to be initiated and then confirmed within `window`
+        /// blocks; `None` lets any amount be transferred directly.
+        #[weight = 0]
+        fn set_large_transfer_confirmation(origin,
+            #[compact] id: T::AssetId,
+            threshold: Option<T::TokenBalance>,
+            window: T::BlockNumber
+        ) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            match threshold {
+                Some(threshold) => {
+                    ensure!(!window.is_zero(), Error::<T>::PeriodZero);
+                    <LargeTransferConfirmation<T>>::insert(id, (threshold, window));
+                }
+                None => <LargeTransferConfirmation<T>>::remove(id),
+            }
+
+            Self::deposit_event(RawEvent::LargeTransferConfirmationSet(id, threshold, window));
+        }
+
+        /// Take `amount` from the caller for a transfer to `target` that completes on confirmation.
+        #[weight = 0]
+        fn initiate_transfer(origin,
+            #[compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::TokenBalance
+        ) {
+            let origin = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            Self::inner_initiate_transfer(&id, &origin, &target, amount)?;
+        }
+
+        /// Complete the caller's pending transfer, or return the tokens if it has expired.
+        #[weight = 0]
+        fn confirm_transfer(origin, #[compact] id: T::AssetId) {
+            let origin = ensure_signed(origin)?;
+
+            Self::inner_confirm_transfer(&id, &origin)?;
+        }
+
+        /// Drop the caller's pending transfer and return the tokens.
+        #[weight = 0]
+        fn cancel_transfer(origin, #[compact] id: T::AssetId) {
+            let origin = ensure_signed(origin)?;
+
+            Self::inner_cancel_transfer(&id, &origin)?;
+        }
+
+        /// Send the caller's whole balance of an asset to `to` and make `to` its owner.
+        #[weight = 0]
+        fn transfer_entire_asset(origin,
+            #[compact] id: T::AssetId,
+            to: <T::Lookup as StaticLookup>::Source
+        ) {
+            let origin = ensure_signed(origin)?;
+            let to = T::Lookup::lookup(to)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            let balance = Self::balance_of(&id, &origin);
+            if !balance.is_zero() {
+                Self::inner_transfer(&id, &origin, &to, balance)?;
+            }
+            <Owners<T>>::insert(id, &to);
+
+            Self::deposit_event(RawEvent::OwnershipChanged(id, origin, to));
+        }
+
+        /// Hand the asset over to `new_owner` and rename it to `new_info`. Decimals can't change,
+        /// as they fix how every stored amount is read.
+        #[weight = 0]
+        fn rebrand(origin,
+            #[compact] id: T::AssetId,
+            new_owner: <T::Lookup as StaticLookup>::Source,
+            new_info: AssetInfo
+        ) {
+            let origin = ensure_signed(origin)?;
+            let new_owner = T::Lookup::lookup(new_owner)?;
+            Self::ensure_owner(&id, &origin)?;
+            let info = Self::asset_info(&id).ok_or(Error::<T>::AssetNotExists)?;
+            ensure!(new_info.decimals == info.decimals, Error::<T>::DecimalsImmutable);
+
+            <Owners<T>>::insert(id, &new_owner);
+            <AssetInfos<T>>::insert(id, &new_info);
+
+            Self::deposit_event(RawEvent::OwnershipChanged(id, origin, new_owner));
+            Self::deposit_event(RawEvent::MetadataChanged(id, new_info));
+        }
+
+        /// Register the address the caller may move its balance to with `emergency_withdraw`.
+        /// Can't be changed while the asset is frozen.
+        #[weight = 0]
+        fn set_recovery(origin, #[compact] id: T::AssetId, addr: <T::Lookup as StaticLookup>::Source) {
+            let origin = ensure_signed(origin)?;
+            let addr = T::Lookup::lookup(addr)?;
+            ensure!(Self::asset_info(&id).is_some(), Error::<T>::AssetNotExists);
+            ensure!(!Self::is_asset_frozen(&id), Error::<T>::AssetFrozen);
+
+            <RecoveryAddresses<T>>::insert((id, &origin), &addr);
+
+            Self::deposit_event(RawEvent::RecoverySet(id, origin, addr));
+        }
+
+        /// Move the caller's unlocked balance to its recovery address while the asset is frozen.
+        /// Every other transfer restriction still applies.
+        #[weight = 0]
+        fn emergency_withdraw(origin, #[compact] id: T::AssetId, to: <T::Lookup as StaticLookup>::Source) {
+            let origin = ensure_signed(origin)?;
+            let to = T::Lookup::lookup(to)?;
+
+            Self::inner_emergency_withdraw(&id, &origin, &to)?;
+        }
+
+        /// Use `amount` of the caller's allowance from `owner` to hold that much of `owner`'s
+        /// balance in reserve for the caller.
+        #[weight = 0]
+        fn reserve_allowance(origin,
+            #[compact] id: T::AssetId,
+            owner: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::TokenBalance
+        ) {
+            let spender = ensure_signed(origin)?;
+            let owner = T::Lookup::lookup(owner)?;
+
+            Self::inner_reserve_allowance(&id, &owner, &spender, amount)?;
+        }
+
+        /// Hold `amount` of the caller's balance in reserve for `spender` straight away, as if
+        /// approving and reserving it in one go. Any allowance `spender` already has is left as
+        /// it is, and reported in the `Approval` event.
+        #[weight = 0]
+        fn approve_and_reserve(origin,
+            #[compact] id: T::AssetId,
+            spender: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::TokenBalance
+        ) {
+            let owner = ensure_signed(origin)?;
+            let spender = T::Lookup::lookup(spender)?;
+            ensure!(owner == spender || Self::allow_delegation(&id), Error::<T>::DelegationDisabled);
+            Self::ensure_allowance_within_cap(&id, amount)?;
+            let new_balance = Self::ensure_can_transfer(&id, &owner, &spender, amount)?;
+
+            let allowance = Self::allowances(&id, &owner, &spender);
+            Self::deposit_event(RawEvent::Approval(id, owner.clone(), spender.clone(), allowance));
+            Self::reserve(&id, &owner, &spender, new_balance, amount);
+        }
+
+        /// Move `amount` of the caller's balance out of circulation for good. The tokens stay part
+        /// of the supply but can never be moved again.
+        #[weight = 0]
+        fn lock_forever(origin, #[compact] id: T::AssetId, #[compact] amount: T::TokenBalance) {
+            let origin = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+            let new_balance = Self::ensure_can_burn(&id, &origin, amount)?;
+
+            Self::set_balance(&id, &origin, new_balance);
+            <PermanentlyLocked<T>>::mutate(id, |locked| *locked = locked.saturating_add(amount));
+
+            Self::deposit_event(RawEvent::LockedForever(id, origin, amount));
+        }
+
+        /// Commit to a transfer by the hash of its SCALE-encoded `(id, target, amount, salt)`,
+        /// to be made by `reveal_transfer` no sooner than `RevealDelay` blocks later.
+        #[weight = 0]
+        fn commit_transfer(origin, hash: T::Hash) {
+            let origin = ensure_signed(origin)?;
+
+            <TransferCommitments<T>>::insert((&origin, hash), <frame_system::Module<T>>::block_number());
+
+            Self::deposit_event(RawEvent::TransferCommitted(origin, hash));
+        }
+
+        /// Make a transfer committed to by `commit_transfer`.
+        #[weight = 0]
+        fn reveal_transfer(origin,
+            #[compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::TokenBalance,
+            salt: [u8; 32]
+        ) {
+            let origin = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            Self::inner_reveal_transfer(&id, &origin, &target, amount, salt)?;
+        }
+
+        /// Pay `amount` the caller holds in reserve against `owner` to `target`; sending it to
+        /// `owner` returns it.
+        #[weight = 0]
+        fn release_reserved(origin,
+            #[compact] id: T::AssetId,
+            owner: <T::Lookup as StaticLookup>::Source,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::TokenBalance
+        ) {
+            let spender = ensure_signed(origin)?;
+            let owner = T::Lookup::lookup(owner)?;
+            let target = T::Lookup::lookup(target)?;
+
+            Self::inner_release_reserved(&id, &owner, &spender, &target, amount)?;
+        }
+
+        /// Transfer like `transfer`, tagging the transfer's event with `tag` for off-chain
+        /// correlation. The tag isn't stored.
+        #[weight = 0]
+        fn transfer_tagged(origin,
+            #[compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::TokenBalance,
+            tag: u32
+        ) {
+            let origin = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            Self::inner_transfer(&id, &origin, &target, amount)?;
+
+            Self::deposit_event(RawEvent::TransferredTagged(id, origin, target, amount, tag));
+        }
+
+        /// Restore `amount` of tokens sent to the burn address by minting it to `to`.
+        #[weight = 0]
+        fn rescue_from_burn_address(origin,
+            #[compact] id: T::AssetId,
+            to: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::TokenBalance
+        ) {
+            ensure_root(origin)?;
+            let to = T::Lookup::lookup(to)?;
+            ensure!(Self::asset_info(&id).is_some(), Error::<T>::AssetNotExists);
+            ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+            let remaining = Self::burned_to_address(id)
+                .checked_sub(&amount)
+                .ok_or(Error::<T>::RescueExceedsBurned)?;
+
+            <BurnedToAddress<T>>::insert(id, remaining);
+            Self::set_balance(&id, &to, Self::balance_of(&id, &to).saturating_add(amount));
+            Self::note_acquired(&id, &to);
+            <TotalSupply<T>>::mutate(id, |supply| *supply = supply.saturating_add(amount));
+
+            Self::deposit_event(RawEvent::Minted(id, to, amount));
+        }
+
+        /// Multiply every balance of an asset by `numerator / denominator`.
+        /// Stored balances are left untouched; each is scaled when read. Amounts set in token
+        /// units, such as allowances, the minimum balance and vesting schedules, are not rebased.
+        ///
+        /// The supply is tracked as the scaled sum of raw balances plus the tokens held outside
+        /// them, which are not rebased. The raw dust between the rebased supply and that sum goes
+        /// to the owner, as far as the new scaling factor can represent it; whatever it cannot is
+        /// dropped from the supply. Since each balance is rounded down on its own, the sum of
+        /// balances can fall short of the supply by less than one token per holder.
+        #[weight = 0]
+        fn rebase(origin, #[compact] id: T::AssetId, numerator: u32, denominator: u32) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            Self::inner_rebase(&id, &origin, numerator, denominator)?;
+        }
+
+        /// Remove an asset whose whole supply has been burned. Its id is never reused.
+        #[weight = 0]
+        fn destroy(origin, #[compact] id: T::AssetId) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+            ensure!(Self::total_supply(&id).is_zero(), Error::<T>::SupplyNotZero);
+            ensure!(Self::distribution(id).is_none(), Error::<T>::DistributionInProgress);
+
+            Self::finish_destroy(&id);
+        }
+
+        /// Register `alias` as a name others can transfer to the caller by.
+        #[weight = 0]
+        fn register_alias(origin, alias: Vec<u8>) {
+            let origin = ensure_signed(origin)?;
+            ensure!(!alias.is_empty() && alias.len() <= MAX_ALIAS_LENGTH, Error::<T>::InvalidAlias);
+            ensure!(!<Aliases<T>>::contains_key(&alias), Error::<T>::AliasTaken);
+
+            <Aliases<T>>::insert(&alias, &origin);
+
+            Self::deposit_event(RawEvent::AliasRegistered(origin, alias));
+        }
+
+        /// Release an alias registered by the caller.
+        #[weight = 0]
+        fn unregister_alias(origin, alias: Vec<u8>) {
+            let origin = ensure_signed(origin)?;
+            ensure!(Self::alias(&alias).as_ref() == Some(&origin), Error::<T>::UnknownAlias);
+
+            <Aliases<T>>::remove(&alias);
+
+            Self::deposit_event(RawEvent::AliasUnregistered(origin, alias));
+        }
+
+        /// Transfer to the account registered under `alias`.
+        #[weight = 0]
+        fn transfer_to_alias(origin,
+            #[compact] id: T::AssetId,
+            alias: Vec<u8>,
+            #[compact] amount: T::TokenBalance
+        ) {
+            let origin = ensure_signed(origin)?;
+            let target = Self::alias(&alias).ok_or(Error::<T>::UnknownAlias)?;
+
+            Self::inner_transfer(&id, &origin, &target, amount)?;
+        }
+
+        /// Agree to take over the balance and allowances of `from` through `merge_accounts`.
+        #[weight = 0]
+        fn accept_merge(origin, #[compact] id: T::AssetId, from: <T::Lookup as StaticLookup>::Source) {
+            let origin = ensure_signed(origin)?;
+            let from = T::Lookup::lookup(from)?;
+            ensure!(Self::asset_info(&id).is_some(), Error::<T>::AssetNotExists);
+
+            <MergeAcceptances<T>>::insert((id, &origin), &from);
+
+            Self::deposit_event(RawEvent::MergeAccepted(id, from, origin));
+        }
+
+        /// Move the caller's whole balance to `to` and re-grant the caller's allowances from `to`.
+        /// `from` must be the caller, and `to` must have accepted the merge with `accept_merge`.
+        #[weight = 0]
+        fn merge_accounts(origin,
+            #[compact] id: T::AssetId,
+            from: <T::Lookup as StaticLookup>::Source,
+            to: <T::Lookup as StaticLookup>::Source
+        ) {
+            let origin = ensure_signed(origin)?;
+            let from = T::Lookup::lookup(from)?;
+            let to = T::Lookup::lookup(to)?;
+            ensure!(from == origin, Error::<T>::NotOwner);
+
+            Self::inner_merge_accounts(&id, &from, &to)?;
+        }
+
+        /// Recount `HolderCount` and `AllowanceCount` of an asset from storage, visiting `max`
+        /// balance and allowance entries of any asset per call. Changes to entries already
+        /// counted are applied to the recount, which replaces the counters once it is done.
+        #[weight = 0]
+        fn recompute_counts(origin, #[compact] id: T::AssetId, max: u32) {
+            ensure_root(origin)?;
+            ensure!(Self::asset_info(&id).is_some(), Error::<T>::AssetNotExists);
+            ensure!(!max.is_zero(), Error::<T>::AmountZero);
+
+            Self::inner_recompute_counts(&id, max);
+        }
+
+        /// Set the asset the caller wants to be paid in by `pay`, or `None` to clear it.
+        #[weight = 0]
+        fn set_preferred_asset(origin, id: Option<T::AssetId>) {
+            let origin = ensure_signed(origin)?;
+            if let Some(id) = id {
+                ensure!(Self::asset_info(&id).is_some(), Error::<T>::AssetNotExists);
+            }
+
+            <PreferredAsset<T>>::mutate(&origin, |preferred| *preferred = id);
+
+            Self::deposit_event(RawEvent::PreferredAssetSet(origin, id));
+        }
+
+        /// Value `pay` converts native amounts into the asset at: `rate` token units per native
+        /// unit. A zero rate stops the asset being used for payments.
+        #[weight = 0]
+        fn set_exchange_rate(origin, #[compact] id: T::AssetId, #[compact] rate: T::TokenBalance) {
+            let origin = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &origin)?;
+
+            if rate.is_zero() {
+                <ExchangeRates<T>>::remove(id);
+            } else {
+                <ExchangeRates<T>>::insert(id, rate);
+            }
+
+            Self::deposit_event(RawEvent::ExchangeRateSet(id, rate));
+        }
+
+        /// Pay `to` the equivalent of `native_amount` in its preferred asset, from the caller's
+        /// balance of that asset.
+        #[weight = 0]
+        fn pay(origin, to: <T::Lookup as StaticLookup>::Source, #[compact] native_amount: BalanceOf<T>) {
+            let origin = ensure_signed(origin)?;
+            let to = T::Lookup::lookup(to)?;
+
+            Self::inner_pay(&origin, &to, native_amount)?;
+        }
+
+        /// Burn each `(from, amount)` out of the caller's allowance from `from`. If any burn would
+        /// fail, none is made.
+        #[weight = 0]
+        fn burn_from_batch(origin,
+            #[compact] id: T::AssetId,
+            burns: Vec<(<T::Lookup as StaticLookup>::Source, T::TokenBalance)>
+        ) {
+            let spender = ensure_signed(origin)?;
+            ensure!(burns.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+            let burns = burns
+                .into_iter()
+                .map(|(from, amount)| Ok((T::Lookup::lookup(from)?, amount)))
+                .collect::<Result<Vec<_>, DispatchError>>()?;
+
+            Self::inner_burn_from_batch(&id, &spender, burns)?;
+        }
+
+        /// Transfer `amount_each` to every recipient. Fails before any transfer if the caller
+        /// doesn't hold enough for all of them, or if a recipient is the caller or listed twice.
+        #[weight = T::WeightInfo::transfer().saturating_mul(recipients.len() as Weight)]
+        fn airdrop_equal(origin,
+            #[compact] id: T::AssetId,
+            recipients: Vec<<T::Lookup as StaticLookup>::Source>,
+            #[compact] amount_each: T::TokenBalance
+        ) {
+            let origin = ensure_signed(origin)?;
+            ensure!(recipients.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+            let recipients = recipients
+                .into_iter()
+                .map(T::Lookup::lookup)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Self::inner_airdrop_equal(&id, &origin, &recipients, amount_each)?;
+        }
+
+        /// Forget the asset's recorded transfer volume, e.g. after a reporting period.
+        #[weight = 0]
+        fn reset_volume(origin, #[compact] id: T::AssetId) {
+            ensure_root(origin)?;
+            ensure!(Self::asset_info(&id).is_some(), Error::<T>::AssetNotExists);
+
+            <BlockVolumes<T>>::remove(id);
+
+            Self::deposit_event(RawEvent::VolumeReset(id));
+        }
+
+        /// Backfill `SpenderAllowances`, `OwnerAllowances` and `RawSupply` from the allowances and
+        /// balances stored before they were introduced.
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = T::DbWeight::get().reads(3);
+
+            if !SpenderAllowancesIndexed::get() {
+                let mut indexed: Weight = 0;
+                for ((id, owner, spender), amount) in <Allowances<T>>::iter() {
+                    if !amount.is_zero() {
+                        <SpenderAllowances<T>>::insert(spender, (id, owner), true);
+                    }
+                    indexed = indexed.saturating_add(1);
+                }
+                SpenderAllowancesIndexed::put(true);
+                weight = weight
+                    .saturating_add(T::DbWeight::get().reads_writes(indexed, indexed.saturating_add(1)));
+            }
+
+            if !OwnerAllowancesIndexed::get() {
+                let mut indexed: Weight = 0;
+                for ((id, owner, spender), amount) in <Allowances<T>>::iter() {
+                    if !amount.is_zero() {
+                        <OwnerAllowances<T>>::insert((id, owner), spender, true);
+                    }
+                    indexed = indexed.saturating_add(1);
+                }
+                OwnerAllowancesIndexed::put(true);
+                weight = weight
+                    .saturating_add(T::DbWeight::get().reads_writes(indexed, indexed.saturating_add(1)));
+            }
+
+            if !RawSupplyIndexed::get() {
+                // Recomputed from scratch, so totals kept by balance writes are not counted twice.
+                <RawSupply<T>>::remove_all();
+                let mut indexed: Weight = 0;
+                for ((id, _), balance) in <Balances<T>>::iter() {
+                    <RawSupply<T>>::mutate(id, |supply| *supply = supply.saturating_add(balance));
+                    indexed = indexed.saturating_add(1);
+                }
+                RawSupplyIndexed::put(true);
+                weight = weight.saturating_add(
+                    T::DbWeight::get().reads_writes(indexed.saturating_mul(2), indexed.saturating_add(1)),
+                );
+            }
+
+            weight
+        }
+
+        fn on_finalize(_n: T::BlockNumber) {
+            for (id, (count, volume)) in <TransferSummaries<T>>::drain() {
+                Self::deposit_event(RawEvent::TransfersAggregated(id, count, volume));
+            }
+        }
+    }
+}
+
+decl_event! {
+    pub enum Event<T> where
+        <T as frame_system::Trait>::AccountId,
+        <T as frame_system::Trait>::BlockNumber,
+        <T as Trait>::TokenBalance,
+        <T as Trait>::AssetId,
+        <T as frame_system::Trait>::Hash,
+        Balance = BalanceOf<T>,
+    {
+        Issued(AssetId, AccountId, TokenBalance),
+        Transferred(AssetId, AccountId, AccountId, TokenBalance),
+        Approval(AssetId, AccountId, AccountId, TokenBalance),
+
+        Minted(AssetId, AccountId, TokenBalance),
+        Burned(AssetId, AccountId, TokenBalance),
+
+        AggregateTransferEventsSet(AssetId, bool),
+        /// Number and total amount of the transfers of an aggregated asset in this block.
+        TransfersAggregated(AssetId, u32, TokenBalance),
+        TransferGateSet(AssetId, AccountId, bool),
+        MinHoldBlocksSet(AssetId, BlockNumber),
+        TransferFeeSet(AssetId, u16),
+        FeeCollectorSet(AssetId, AccountId),
+        Frozen(AssetId, AccountId),
+        Thawed(AssetId, AccountId),
+        AssetFrozen(AssetId),
+        AssetThawed(AssetId),
+        FreezeAuthorityRenounced(AssetId),
+        AllowDelegationSet(AssetId, bool),
+        RestrictDestinationsSet(AssetId, bool),
+        MaxSupplySet(AssetId, Option<TokenBalance>),
+        MintingFrozen(AssetId),
+        MaxAllowanceSet(AssetId, Option<TokenBalance>),
+        TradingStartBlockSet(AssetId, BlockNumber),
+        EnumerableSet(AssetId, bool),
+        EventVerbositySet(AssetId, EventVerbosity),
+        /// Asset can no longer be transferred or minted, only burned.
+        AssetWoundDown(AssetId),
+        /// Amount of a transfer of an asset with `MinimalAmountOnly` event verbosity.
+        TransferredAmount(AssetId, TokenBalance),
+        /// Standing approval of a spender, replenished to the amount once per block period.
+        StandingApprovalSet(AssetId, AccountId, AccountId, TokenBalance, BlockNumber),
+        MinBalanceSet(AssetId, TokenBalance),
+        /// The minimum balance was raised from the first to the second amount; holders below
+        /// the new minimum keep their balances.
+        MinBalanceRaised(AssetId, TokenBalance, TokenBalance),
+        MinPrecisionSet(AssetId, Option<u8>),
+        DisplayDecimalsSet(AssetId, Option<u8>),
+        MinterAdded(AssetId, AccountId),
+        MinterRemoved(AssetId, AccountId),
+        /// Tokens minted to an account are locked, vesting from the given block on.
+        VestingCreated(AssetId, AccountId, TokenBalance, BlockNumber),
+        /// Redemption rate in native units per token unit, and the reserve paying out.
+        RedemptionSet(AssetId, Balance, AccountId),
+        /// Tokens burned by an account and the native amount paid for them.
+        Redeemed(AssetId, AccountId, TokenBalance, Balance),
+        /// Asset accepted for transaction fees and the token units charged per native unit.
+        FeeAssetSet(AssetId, TokenBalance),
+        /// Transaction fee paid by an account in the fee asset.
+        TransactionFeePaid(AssetId, AccountId, TokenBalance),
+        /// Every holder of an asset was paid its share of a reward in another asset.
+        Distributed(AssetId, AssetId, TokenBalance),
+        /// Threshold above which transfers need confirmation, and the confirmation window.
+        LargeTransferConfirmationSet(AssetId, Option<TokenBalance>, BlockNumber),
+        /// Transfer from an account to another awaiting confirmation by the given block.
+        TransferInitiated(AssetId, AccountId, AccountId, TokenBalance, BlockNumber),
+        /// Pending transfer dropped by its sender; the amount was returned.
+        PendingTransferCancelled(AssetId, AccountId, TokenBalance),
+        /// Pending transfer not confirmed in time; the amount was returned.
+        PendingTransferExpired(AssetId, AccountId, TokenBalance),
+        /// Ownership of an asset passed from the first account to the second.
+        OwnershipChanged(AssetId, AccountId, AccountId),
+        /// Recovery address registered by an account.
+        RecoverySet(AssetId, AccountId, AccountId),
+        /// Balance of an owner held in reserve for a spender out of its allowance.
+        AllowanceReserved(AssetId, AccountId, AccountId, TokenBalance),
+        /// Reserve of an owner released by a spender to a target.
+        ReserveReleased(AssetId, AccountId, AccountId, AccountId, TokenBalance),
+        /// Transfer made with `transfer_tagged` and its tag.
+        TransferredTagged(AssetId, AccountId, AccountId, TokenBalance, u32),
+        /// Balances and supply of an asset multiplied by numerator / denominator.
+        Rebased(AssetId, u32, u32),
+        Destroyed(AssetId),
+        AliasRegistered(AccountId, Vec<u8>),
+        /// Holder and allowance counts of an asset recounted from storage.
+        CountsRecomputed(AssetId, u32, u32),
+        /// Second account agreed to take over the first account's holding of an asset.
+        MergeAccepted(AssetId, AccountId, AccountId),
+        PreferredAssetSet(AccountId, Option<AssetId>),
+        /// Token units of an asset paid per native unit by `pay`.
+        ExchangeRateSet(AssetId, TokenBalance),
+        /// Payment from an account to another of a native amount, settled in an asset.
+        Paid(AccountId, AccountId, AssetId, Balance, TokenBalance),
+        /// Name, symbol and decimals of an asset.
+        MetadataSet(AssetId, AssetInfo),
+        AliasUnregistered(AccountId, Vec<u8>),
+        AllowedDestinationSet(AssetId, AccountId, bool),
+        /// Balance of an account permanently taken out of circulation.
+        LockedForever(AssetId, AccountId, TokenBalance),
+        /// Account committed to a transfer by its hash.
+        TransferCommitted(AccountId, Hash),
+        /// Name and symbol of an asset replaced by `rebrand`.
+        MetadataChanged(AssetId, AssetInfo),
+        WashTradeProtectionSet(AssetId, bool),
+        RelatedAccountsSet(AssetId, AccountId, AccountId, bool),
+        /// Recorded transfer volume of an asset cleared by root.
+        VolumeReset(AssetId),
+    }
+}
+
+decl_error! {
+    pub enum Error for Module<T: Trait> {
+        BalanceLow,
+        BalanceZero,
+        AllowanceLow,
+        AmountZero,
+        AssetNotExists,
+        NotOwner,
+        TransfersDisabled,
+        HoldPeriodActive,
+        InvalidFee,
+        AccountFrozen,
+        BatchTooLarge,
+        DelegationDisabled,
+        BadNonce,
+        BadSignature,
+        DestinationNotAllowed,
+        SupplyCapExceeded,
+        MintingFrozen,
+        PeriodZero,
+        BalanceTooLow,
+        NotMinter,
+        VestingExists,
+        BalanceLocked,
+        InvalidPrecision,
+        AssetFrozen,
+        FreezeRenounced,
+        RedemptionDisabled,
+        ReserveInsufficient,
+        DistributionInProgress,
+        ConfirmationRequired,
+        ConfirmationNotRequired,
+        TransferPending,
+        NoPendingTransfer,
+        AllowanceTooLarge,
+        NotRecoveryAddress,
+        TradingNotStarted,
+        ReserveLow,
+        RescueExceedsBurned,
+        InvalidRebase,
+        SupplyNotZero,
+        AssetWoundDown,
+        InvalidAlias,
+        AliasTaken,
+        UnknownAlias,
+        NoPreferredAsset,
+        NoExchangeRate,
+        BadReveal,
+        TooEarly,
+        DecimalsImmutable,
+        WashTradeBlocked,
+        MergeNotAccepted,
+        AssetNotFrozen,
+        AmountTooSmall,
+        /// An amount can't be converted between token and native units.
+        ConversionFailed,
+        /// A recipient is the sender or is listed more than once.
+        InvalidRecipient,
+    }
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as Assets {
+        TotalSupply: map hasher(twox_64_concat) T::AssetId => T::TokenBalance;
+        /// Supply the asset was issued with, unaffected by later mints and burns.
+        InitialSupply get(fn initial_supply): map hasher(twox_64_concat) T::AssetId => T::TokenBalance;
+        AssetInfos: map hasher(twox_64_concat) T::AssetId => Option<AssetInfo>;
+        /// Raw balances; the balance of an account is its raw balance times the scaling factor.
+        Balances: map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => T::TokenBalance;
+        /// Product of an asset's rebases, in parts per `SCALING_ACCURACY`; one if never rebased.
+        ScalingFactor get(fn scaling_factor): map hasher(twox_64_concat) T::AssetId => Option<u128>;
+        /// Sum of the stored raw balances of an asset.
+        RawSupply get(fn raw_supply): map hasher(twox_64_concat) T::AssetId => T::TokenBalance;
+        /// Whether `RawSupply` has been backfilled from the balances stored before it existed.
+        RawSupplyIndexed: bool;
+        NextAssetId get(fn next_asset_id): T::AssetId;
+        Allowances: map hasher(blake2_128_concat) (T::AssetId, T::AccountId, T::AccountId) => T::TokenBalance;
+        Owners get(fn owner): map hasher(twox_64_concat) T::AssetId => Option<T::AccountId>;
+        AggregateTransferEvents: map hasher(twox_64_concat) T::AssetId => bool;
+        TransferEventVerbosity get(fn event_verbosity): map hasher(twox_64_concat) T::AssetId => EventVerbosity;
+        /// Transfer count and volume of aggregated assets in the current block, cleared in `on_finalize`.
+        TransferSummaries: map hasher(twox_64_concat) T::AssetId => (u32, T::TokenBalance);
+        /// Number of accounts with a non-zero balance of an asset.
+        HolderCount get(fn holder_count): map hasher(twox_64_concat) T::AssetId => u32;
+        /// Number of non-zero allowances of an asset.
+        AllowanceCount get(fn allowance_count): map hasher(twox_64_concat) T::AssetId => u32;
+        /// Index of the non-zero allowances granted to each spender, by asset and owner.
+        SpenderAllowances:
+            double_map hasher(blake2_128_concat) T::AccountId,
+            hasher(blake2_128_concat) (T::AssetId, T::AccountId) => bool;
+        /// Whether `SpenderAllowances` has been backfilled with allowances granted before it existed.
+        SpenderAllowancesIndexed: bool;
+        /// Index of the non-zero allowances each owner has granted of an asset, by spender.
+        OwnerAllowances:
+            double_map hasher(blake2_128_concat) (T::AssetId, T::AccountId),
+            hasher(blake2_128_concat) T::AccountId => bool;
+        /// Whether `OwnerAllowances` has been backfilled with allowances granted before it existed.
+        OwnerAllowancesIndexed: bool;
+        /// Account whose holding of an asset the keyed account agreed to take over.
+        MergeAcceptances get(fn merge_acceptance):
+            map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => Option<T::AccountId>;
+        /// Whether an account may send an asset; maintained by the asset owner or another module.
+        TransferGate get(fn transfer_gate): map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => bool = true;
+        MinHoldBlocks get(fn min_hold_blocks): map hasher(twox_64_concat) T::AssetId => T::BlockNumber;
+        /// First block in which an asset can be transferred.
+        TradingStartBlock get(fn trading_start_block): map hasher(twox_64_concat) T::AssetId => T::BlockNumber;
+        /// Block in which an account last received an asset with a hold period. Any incoming
+        /// transfer or mint restarts the hold period for the account's whole balance; accounts
+        /// without a recorded receipt are not held.
+        AcquiredAt get(fn acquired_at): map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => Option<T::BlockNumber>;
+        /// Transfer fee of an asset in basis points.
+        TransferFees get(fn transfer_fee): map hasher(twox_64_concat) T::AssetId => u16;
+        FeeCollectors: map hasher(twox_64_concat) T::AssetId => Option<T::AccountId>;
+        Frozen get(fn is_frozen): map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => bool;
+        AssetFrozen get(fn is_asset_frozen): map hasher(twox_64_concat) T::AssetId => bool;
+        FreezeRenounced get(fn is_freeze_renounced): map hasher(twox_64_concat) T::AssetId => bool;
+        /// Whether holders may approve spenders; when disabled only owners spend their own tokens.
+        AllowDelegation get(fn allow_delegation): map hasher(twox_64_concat) T::AssetId => bool = true;
+        /// Nonce expected in the next signed transfer of an account.
+        TransferNonce get(fn transfer_nonce): map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => u32;
+        /// Nonce expected in the next permit signed by an account.
+        PermitNonce get(fn permit_nonce): map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => u32;
+        RestrictDestinations get(fn restrict_destinations): map hasher(twox_64_concat) T::AssetId => bool;
+        AllowedDestinations get(fn is_allowed_destination): map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => bool;
+        MaxSupply get(fn max_supply): map hasher(twox_64_concat) T::AssetId => Option<T::TokenBalance>;
+        /// Largest amount a single approval of an asset may grant.
+        MaxAllowance get(fn max_allowance): map hasher(twox_64_concat) T::AssetId => Option<T::TokenBalance>;
+        /// Whether an asset is being deprecated: only burns are still allowed.
+        WoundDown get(fn is_wound_down): map hasher(twox_64_concat) T::AssetId => bool;
+        MintFrozen get(fn is_mint_frozen): map hasher(twox_64_concat) T::AssetId => bool;
+        /// Smallest balance an account should hold; zero if the asset has no minimum.
+        MinBalance get(fn min_balance): map hasher(twox_64_concat) T::AssetId => T::TokenBalance;
+        /// Number of decimals transfers may use, e.g. zero for whole units only.
+        MinPrecision get(fn min_precision): map hasher(twox_64_concat) T::AssetId => Option<u8>;
+        /// Decimals frontends should show an asset's balances with.
+        DisplayDecimals get(fn display_decimals): map hasher(twox_64_concat) T::AssetId => Option<u8>;
+        /// `(asset, from, to)` triples with at least one transfer, if `TrackCounterparties` is on.
+        Counterparties: map hasher(blake2_128_concat) (T::AssetId, T::AccountId, T::AccountId) => bool;
+        /// Accounts other than the owner allowed to mint an asset.
+        Minters get(fn is_minter): map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => bool;
+        Vesting get(fn vesting):
+            map hasher(blake2_128_concat) (T::AssetId, T::AccountId)
+            => Option<VestingSchedule<T::TokenBalance, T::BlockNumber>>;
+        /// Redemption rate and the reserve account paying redemptions out.
+        Redemptions get(fn redemption): map hasher(twox_64_concat) T::AssetId => Option<(BalanceOf<T>, T::AccountId)>;
+        /// Asset transaction fees may be paid in and the token units charged per native unit.
+        FeeAsset get(fn fee_asset): Option<(T::AssetId, T::TokenBalance)>;
+        /// Distribution to an asset's holders that has not paid every holder yet.
+        Distributions get(fn distribution):
+            map hasher(twox_64_concat) T::AssetId => Option<Distribution<T::AssetId, T::TokenBalance>>;
+        /// Balances as of the start of a distribution in progress, for holders not paid yet.
+        DistributionSnapshots get(fn distribution_snapshot):
+            double_map hasher(twox_64_concat) T::AssetId, hasher(blake2_128_concat) T::AccountId
+            => Option<T::TokenBalance>;
+        /// Last `MAX_RECENT_TRANSFERS` transfers of an asset as `(from, to, amount, block)`,
+        /// oldest first.
+        RecentTransfers get(fn recent_transfers):
+            map hasher(twox_64_concat) T::AssetId => Vec<(T::AccountId, T::AccountId, T::TokenBalance, T::BlockNumber)>;
+        /// Amount above which transfers must be confirmed, and the blocks allowed for confirming.
+        LargeTransferConfirmation get(fn large_transfer_confirmation):
+            map hasher(twox_64_concat) T::AssetId => Option<(T::TokenBalance, T::BlockNumber)>;
+        PendingTransfers get(fn pending_transfer):
+            map hasher(blake2_128_concat) (T::AssetId, T::AccountId)
+            => Option<PendingTransfer<T::AccountId, T::TokenBalance, T::BlockNumber>>;
+        /// Block at which an account committed to a transfer by its hash.
+        TransferCommitments get(fn transfer_commitment):
+            map hasher(blake2_128_concat) (T::AccountId, T::Hash) => Option<T::BlockNumber>;
+        /// Address an account may withdraw to while the asset is frozen.
+        RecoveryAddresses get(fn recovery_address):
+            map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => Option<T::AccountId>;
+        /// Balance taken from an owner and held for a spender by `reserve_allowance`.
+        Reserved get(fn reserved):
+            map hasher(blake2_128_concat) (T::AssetId, T::AccountId, T::AccountId) => T::TokenBalance;
+        /// Tokens of an asset locked by `lock_forever`; part of the supply but never spendable.
+        PermanentlyLocked get(fn permanently_locked): map hasher(twox_64_concat) T::AssetId => T::TokenBalance;
+        /// Whether transfers of an asset between an account and itself or a related account fail.
+        WashTradeProtection get(fn wash_trade_protection): map hasher(twox_64_concat) T::AssetId => bool;
+        /// Pairs of accounts controlled by the same party, stored in both orders.
+        RelatedAccounts get(fn are_related):
+            map hasher(blake2_128_concat) (T::AssetId, T::AccountId, T::AccountId) => bool;
+        /// Amount of an asset sent to the burn address that hasn't been rescued.
+        BurnedToAddress get(fn burned_to_address): map hasher(twox_64_concat) T::AssetId => T::TokenBalance;
+        /// Whether `top_holders` and `holder_rank` list an asset's holders.
+        Enumerable get(fn is_enumerable): map hasher(twox_64_concat) T::AssetId => bool = true;
+        /// Account registered under each alias.
+        Aliases get(fn alias): map hasher(blake2_128_concat) Vec<u8> => Option<T::AccountId>;
+        /// `(holders, allowances, cursor)` of a recount in progress, where `cursor` is the raw
+        /// storage key of the last balance or allowance entry visited.
+        CountRecomputations get(fn count_recomputation): map hasher(twox_64_concat) T::AssetId => Option<(u32, u32, Vec<u8>)>;
+        /// Asset an account wants to receive payments made with `pay` in.
+        PreferredAsset get(fn preferred_asset): map hasher(blake2_128_concat) T::AccountId => Option<T::AssetId>;
+        /// Token units of an asset `pay` charges per native unit.
+        ExchangeRates get(fn exchange_rate): map hasher(twox_64_concat) T::AssetId => Option<T::TokenBalance>;
+        /// `(block, volume)` of the last `MAX_VOLUME_BLOCKS` blocks with transfers of an asset,
+        /// oldest first.
+        BlockVolumes get(fn block_volumes): map hasher(twox_64_concat) T::AssetId => Vec<(T::BlockNumber, T::TokenBalance)>;
+        StandingApprovals get(fn standing_approval):
+            map hasher(blake2_128_concat) (T::AssetId, T::AccountId, T::AccountId)
+            => Option<StandingApproval<T::TokenBalance, T::BlockNumber>>;
+    }
+}
+
+impl<T: Trait> Module<T> {
+    pub fn total_supply(id: &T::AssetId) -> T::TokenBalance {
+        <TotalSupply<T>>::get(id)
+    }
+
+    pub fn largest_assets(n: u32) -> Vec<(T::AssetId, T::TokenBalance)> {
+        let mut assets: Vec<_> = <TotalSupply<T>>::iter().collect();
+        assets.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        assets.truncate(n as usize);
+
+        assets
+    }
+
+    pub fn combined_supply(ids: Vec<T::AssetId>) -> Vec<(T::AssetId, T::TokenBalance)> {
+        ids.into_iter()
+            .filter(|id| Self::asset_info(id).is_some())
+            .map(|id| (id, Self::total_supply(&id)))
+            .collect()
+    }
+
+    pub fn balance_of(id: &T::AssetId, owner: &T::AccountId) -> T::TokenBalance {
+        Self::scaled(id, <Balances<T>>::get((id, owner)))
+    }
+
+    /// Stored raw balance of `who` and the balance it represents after the asset's rebases.
+    pub fn raw_and_scaled_balance(id: &T::AssetId, who: &T::AccountId) -> (T::TokenBalance, T::TokenBalance) {
+        let raw = <Balances<T>>::get((id, who));
+
+        (raw, Self::scaled(id, raw))
+    }
+
+    /// Balance represented by a stored `raw` balance after the asset's rebases, rounded down.
+    fn scaled(id: &T::AssetId, raw: T::TokenBalance) -> T::TokenBalance {
+        match Self::scaling_factor(id) {
+            Some(factor) => multiply_by_rational(raw.saturated_into(), factor, SCALING_ACCURACY)
+                .map(|balance| balance.saturated_into())
+                .unwrap_or_else(|_| T::TokenBalance::max_value()),
+            None => raw,
+        }
+    }
+
+    /// Stored raw balance representing `balance` after the asset's rebases, rounded down.
+    fn unscaled(id: &T::AssetId, balance: T::TokenBalance) -> T::TokenBalance {
+        match Self::scaling_factor(id) {
+            Some(factor) => multiply_by_rational(balance.saturated_into(), SCALING_ACCURACY, factor)
+                .map(|raw| raw.saturated_into())
+                .unwrap_or_else(|_| T::TokenBalance::max_value()),
+            None => balance,
+        }
+    }
+
+    /// `amount` of an asset in native currency, `None` if it can't be represented.
+    pub fn to_native(id: &T::AssetId, amount: T::TokenBalance) -> Option<BalanceOf<T>> {
+        T::BalanceConverter::to_native(id, amount)
+    }
+
+    /// Native `amount` in units of an asset, `None` if it can't be represented.
+    pub fn from_native(id: &T::AssetId, amount: BalanceOf<T>) -> Option<T::TokenBalance> {
+        T::BalanceConverter::from_native(id, amount)
+    }
+
+    /// Estimated weight of a batch of `call_count` transfers, for sizing batches to fit a block.
+    pub fn estimate_batch_weight(call_count: u32) -> Weight {
+        T::WeightInfo::transfer().saturating_mul(call_count.into())
+    }
+
+    pub fn pallet_limits() -> PalletLimits {
+        PalletLimits {
+            max_batch_size: T::MaxBatchSize::get(),
+            max_recent_transfers: MAX_RECENT_TRANSFERS as u32,
+            max_fee_bps: MAX_BPS,
+        }
+    }
+
+    /// Balance of `a` minus balance of `b`, saturating at the bounds of `i128`.
+    pub fn balance_diff(id: &T::AssetId, a: &T::AccountId, b: &T::AccountId) -> i128 {
+        let a: u128 = Self::balance_of(id, a).saturated_into();
+        let b: u128 = Self::balance_of(id, b).saturated_into();
+
+        if a >= b {
+            (a - b).min(i128::max_value() as u128) as i128
+        } else {
+            -((b - a).min(i128::max_value() as u128) as i128)
+        }
+    }
+
+    /// `who`'s balance in basis points of the total supply, zero if there is no supply.
+    pub fn supply_share_bps(id: &T::AssetId, who: &T::AccountId) -> u16 {
+        let supply: u128 = Self::total_supply(id).saturated_into();
+        if supply.is_zero() {
+            return 0;
+        }
+
+        let balance: u128 = Self::balance_of(id, who).saturated_into();
+        multiply_by_rational(balance, MAX_BPS.into(), supply)
+            .map(|share| share.min(MAX_BPS.into()) as u16)
+            .unwrap_or(0)
+    }
+
+    /// Holders of an asset by balance, largest first and ties by account, or none if it isn't
+    /// enumerable.
+    fn ranked_holders(id: &T::AssetId) -> Vec<(T::AccountId, T::TokenBalance)> {
+        if !Self::is_enumerable(id) {
+            return Vec::new();
+        }
+
+        let mut holders: Vec<(T::AccountId, T::TokenBalance)> = <Balances<T>>::iter()
+            .filter(|((asset, _), _)| asset == id)
+            .map(|((_, who), balance)| (who, Self::scaled(id, balance)))
+            .collect();
+        holders.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        holders
+    }
+
+    /// The `n` largest holders of an asset.
+    pub fn top_holders(id: &T::AssetId, n: u32) -> Vec<(T::AccountId, T::TokenBalance)> {
+        let mut holders = Self::ranked_holders(id);
+        holders.truncate(n as usize);
+
+        holders
+    }
+
+    /// One-based position of `who` among the holders of an asset by balance.
+    pub fn holder_rank(id: &T::AssetId, who: &T::AccountId) -> Option<u32> {
+        Self::ranked_holders(id)
+            .iter()
+            .position(|(holder, _)| holder == who)
+            .map(|index| index as u32 + 1)
+    }
+
+    /// `(amount, deadline)` of each two-step transfer of an asset to `who` awaiting its sender's
+    /// confirmation.
+    pub fn pending_transfers(id: &T::AssetId, who: &T::AccountId) -> Vec<(T::TokenBalance, T::BlockNumber)> {
+        <PendingTransfers<T>>::iter()
+            .filter(|((asset, _), pending)| asset == id && pending.target == *who)
+            .map(|(_, pending)| (pending.amount, pending.deadline))
+            .collect()
+    }
+
+    /// A page of the `(spender, amount)` allowances `owner` has granted, sorted by spender and
+    /// starting at the `start_index`-th. Sorting reads every allowance `owner` has granted of the
+    /// asset, but none granted by other owners.
+    pub fn granted_allowances(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        start_index: u32,
+        limit: u32,
+    ) -> Vec<(T::AccountId, T::TokenBalance)> {
+        let mut granted: Vec<(T::AccountId, T::TokenBalance)> =
+            <OwnerAllowances<T>>::iter_prefix((id, owner))
+                .map(|(spender, _)| {
+                    let amount = Self::allowances(id, owner, &spender);
+                    (spender, amount)
+                })
+                .collect();
+        granted.sort_by(|a, b| a.0.cmp(&b.0));
+
+        granted
+            .into_iter()
+            .skip(start_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// `(asset, owner, amount)` of every allowance granted to `spender` on an existing asset.
+    pub fn spendable_assets(spender: &T::AccountId) -> Vec<(T::AssetId, T::AccountId, T::TokenBalance)> {
+        <SpenderAllowances<T>>::iter_prefix(spender)
+            .filter(|((id, _), _)| Self::asset_info(id).is_some())
+            .map(|((id, owner), _)| {
+                let amount = Self::allowances(&id, &owner, spender);
+                (id, owner, amount)
+            })
+            .collect()
+    }
+
+    /// Accounts allowed to mint an asset: its owner, if it has one, followed by the added minters.
+    pub fn minters(id: &T::AssetId) -> Vec<T::AccountId> {
+        let owner = Self::owner(id);
+        owner
+            .clone()
+            .into_iter()
+            .chain(
+                <Minters<T>>::iter()
+                    .filter(|((asset, who), allowed)| {
+                        asset == id && *allowed && Some(who) != owner.as_ref()
+                    })
+                    .map(|((_, who), _)| who),
+            )
+            .collect()
+    }
+
+    /// `(free, reserved, supply)` of an asset, where `free` is the sum of balances and `reserved`
+    /// the tokens held outside balances: reserves, pending transfers, undistributed rewards and
+    /// permanent locks. `free + reserved` equals `supply` until the asset is rebased; after that
+    /// each balance rounds down on its own, so `free` can fall short by less than one token per
+    /// holder.
+    pub fn asset_solvency(id: &T::AssetId) -> (T::TokenBalance, T::TokenBalance, T::TokenBalance) {
+        (Self::free_supply(id), Self::held_supply(id), Self::total_supply(id))
+    }
+
+    /// Sum of the balances of an asset.
+    fn free_supply(id: &T::AssetId) -> T::TokenBalance {
+        <Balances<T>>::iter()
+            .filter(|((asset, _), _)| asset == id)
+            .fold(Zero::zero(), |free: T::TokenBalance, (_, balance)| {
+                free.saturating_add(Self::scaled(id, balance))
+            })
+    }
+
+    /// Tokens of an asset held outside balances: reserves, pending transfers, undistributed
+    /// rewards and permanent locks. These are not scaled by rebases.
+    fn held_supply(id: &T::AssetId) -> T::TokenBalance {
+        let reserves = <Reserved<T>>::iter()
+            .filter(|((asset, _, _), _)| asset == id)
+            .map(|(_, amount)| amount);
+        let pending = <PendingTransfers<T>>::iter()
+            .filter(|((asset, _), _)| asset == id)
+            .map(|(_, pending)| pending.amount);
+        let undistributed = <Distributions<T>>::iter()
+            .filter(|(_, distribution)| distribution.reward_id == *id)
+            .map(|(_, distribution)| distribution.total_reward.saturating_sub(distribution.paid));
+        reserves
+            .chain(pending)
+            .chain(undistributed)
+            .fold(Self::permanently_locked(id), |reserved: T::TokenBalance, amount| {
+                reserved.saturating_add(amount)
+            })
+    }
+
+    /// `(circulating, reserved, vesting_locked, permanently_locked)` parts of an asset's supply.
+    /// `circulating` is the part of balances free to move, `vesting_locked` the part still under
+    /// vesting, `reserved` the tokens held outside balances other than permanent locks. The four
+    /// add up to the supply.
+    pub fn supply_composition(
+        id: &T::AssetId,
+    ) -> (T::TokenBalance, T::TokenBalance, T::TokenBalance, T::TokenBalance) {
+        let permanently_locked = Self::permanently_locked(id);
+        let reserved = Self::held_supply(id).saturating_sub(permanently_locked);
+        let vesting_locked = <Vesting<T>>::iter()
+            .filter(|((asset, _), _)| asset == id)
+            .fold(Zero::zero(), |locked: T::TokenBalance, ((_, who), _)| {
+                let vesting = Self::vesting_locked(id, &who).min(Self::balance_of(id, &who));
+                locked.saturating_add(vesting)
+            });
+        let circulating = Self::free_supply(id).saturating_sub(vesting_locked);
+
+        (circulating, reserved, vesting_locked, permanently_locked)
+    }
+
+    /// Supply of an asset less the tokens permanently locked by `lock_forever`.
+    pub fn circulating_supply(id: &T::AssetId) -> T::TokenBalance {
+        Self::total_supply(id).saturating_sub(Self::permanently_locked(id))
+    }
+
+    /// Whether `who` holds the asset's entire, non-zero supply.
+    pub fn is_sole_holder(id: &T::AssetId, who: &T::AccountId) -> bool {
+        let balance = Self::balance_of(id, who);
+
+        Self::holder_count(id) == 1 && !balance.is_zero() && balance == Self::total_supply(id)
+    }
+
+    /// Share of the supply held by the largest holder, in basis points.
+    pub fn concentration(id: &T::AssetId) -> u16 {
+        let supply: u128 = Self::total_supply(id).saturated_into();
+        if supply.is_zero() {
+            return 0;
+        }
+
+        let largest: u128 = <Balances<T>>::iter()
+            .filter(|((asset, _), _)| asset == id)
+            .map(|(_, balance)| Self::scaled(id, balance))
+            .max()
+            .unwrap_or_else(Zero::zero)
+            .saturated_into();
+        multiply_by_rational(largest, MAX_BPS.into(), supply)
+            .map(|share| share.min(MAX_BPS.into()) as u16)
+            .unwrap_or(0)
+    }
+
+    /// Whether `from` has ever transferred the asset to `to`; always false unless the runtime
+    /// tracks counterparties.
+    pub fn has_transacted(id: &T::AssetId, from: &T::AccountId, to: &T::AccountId) -> bool {
+        <Counterparties<T>>::get((id, from, to))
+    }
+
+    /// Merkle root over the asset's `(account, balance)` pairs, sorted by account.
+    ///
+    /// Leaves are the hash of each SCALE-encoded `(0u8, account, balance)`, inner nodes the hash of
+    /// the SCALE-encoded `(1u8, left, right)`, and an unpaired last node is carried up unchanged.
+    /// The tags keep a leaf from being passed off as an inner node. An asset without holders has
+    /// the default hash as root.
+    pub fn balances_root(id: &T::AssetId) -> T::Hash {
+        const LEAF_TAG: u8 = 0;
+        const NODE_TAG: u8 = 1;
+
+        let mut holders: Vec<(T::AccountId, T::TokenBalance)> = <Balances<T>>::iter()
+            .filter(|((asset, _), _)| asset == id)
+            .map(|((_, who), balance)| (who, Self::scaled(id, balance)))
+            .collect();
+        holders.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut layer: Vec<T::Hash> = holders
+            .iter()
+            .map(|(who, balance)| T::Hashing::hash_of(&(LEAF_TAG, who, balance)))
+            .collect();
+        if layer.is_empty() {
+            return Default::default();
+        }
+
+        while layer.len() > 1 {
+            layer = layer
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => T::Hashing::hash_of(&(NODE_TAG, left, right)),
+                    _ => pair[0],
+                })
+                .collect();
+        }
+
+        layer[0]
+    }
+
+    /// Hash of the SCALE-encoded `(info, config, total_supply, holder_count, balances_root)` of an
+    /// asset, changing whenever its metadata, settings, supply or any balance does. An asset that
+    /// doesn't exist has the default hash.
+    pub fn asset_state_digest(id: &T::AssetId) -> T::Hash {
+        let info = match Self::asset_info(id) {
+            Some(info) => info,
+            None => return Default::default(),
+        };
+
+        let state = (
+            info,
+            Self::asset_config(id),
+            Self::total_supply(id),
+            Self::holder_count(id),
+            Self::balances_root(id),
+        );
+
+        T::Hashing::hash_of(&state)
+    }
+
+    pub fn asset_storage_stats(id: &T::AssetId) -> (u32, u32) {
+        (Self::holder_count(id), Self::allowance_count(id))
+    }
+
+    /// Write a balance, keeping `HolderCount` in sync and removing zero entries.
+    ///
+    /// Once the asset is rebased, the stored raw balance moves by the raw value of the change, so
+    /// that both sides of a transfer move by the same raw amount. The total supply is corrected by
+    /// however much the scaled `RawSupply` moved beyond `new_balance - old_balance`, so that it
+    /// keeps equalling the scaled `RawSupply` plus the tokens held outside balances.
+    fn set_balance(id: &T::AssetId, who: &T::AccountId, new_balance: T::TokenBalance) {
+        if Self::scaling_factor(id).is_none() {
+            return Self::set_raw_balance(id, who, new_balance);
+        }
+
+        let old_raw = <Balances<T>>::get((id, who));
+        let old_balance = Self::scaled(id, old_raw);
+        let new_raw = if new_balance.is_zero() {
+            Zero::zero()
+        } else if new_balance >= old_balance {
+            old_raw.saturating_add(Self::unscaled(id, new_balance - old_balance))
+        } else {
+            old_raw.saturating_sub(Self::unscaled(id, old_balance - new_balance))
+        };
+        let free_before = Self::scaled(id, Self::raw_supply(id));
+        Self::set_raw_balance(id, who, new_raw);
+        let free_after = Self::scaled(id, Self::raw_supply(id));
+
+        <TotalSupply<T>>::mutate(id, |supply| {
+            *supply = supply
+                .saturating_add(free_after)
+                .saturating_add(old_balance)
+                .saturating_sub(free_before)
+                .saturating_sub(new_balance);
+        });
+    }
+
+    /// Fail for a non-zero `amount` too small to change any raw balance of a rebased asset.
+    fn ensure_moves_raw_balance(id: &T::AssetId, amount: T::TokenBalance) -> Result<(), Error<T>> {
+        ensure!(amount.is_zero() || !Self::unscaled(id, amount).is_zero(), Error::<T>::AmountTooSmall);
+
+        Ok(())
+    }
+
+    /// Write a stored raw balance, keeping `HolderCount` and `RawSupply` in sync.
+    fn set_raw_balance(id: &T::AssetId, who: &T::AccountId, new_balance: T::TokenBalance) {
+        let old_balance = <Balances<T>>::get((id, who));
+        Self::note_distribution_balance(id, who, old_balance);
+        <RawSupply<T>>::mutate(id, |supply| {
+            *supply = supply.saturating_add(new_balance).saturating_sub(old_balance)
+        });
+
+        if old_balance.is_zero() && !new_balance.is_zero() {
+            <HolderCount<T>>::mutate(id, |count| *count = count.saturating_add(1));
+            Self::reconcile_recount(id, &<Balances<T>>::hashed_key_for((id, who)), |holders, _| {
+                *holders = holders.saturating_add(1)
+            });
+        } else if !old_balance.is_zero() && new_balance.is_zero() {
+            <HolderCount<T>>::mutate(id, |count| {
+                debug_assert!(*count > 0, "holder count out of sync with balances");
+                *count = count.saturating_sub(1);
+            });
+            Self::reconcile_recount(id, &<Balances<T>>::hashed_key_for((id, who)), |holders, _| {
+                *holders = holders.saturating_sub(1)
+            });
+        }
+
+        if new_balance.is_zero() {
+            <Balances<T>>::remove((id, who));
+        } else {
+            <Balances<T>>::insert((id, who), new_balance);
+        }
+    }
+
+    /// Write an allowance, keeping `AllowanceCount` in sync and removing zero entries.
+    fn set_allowance(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        amount: T::TokenBalance,
+    ) {
+        let old_amount = <Allowances<T>>::get((id, owner, spender));
+
+        if old_amount.is_zero() && !amount.is_zero() {
+            <AllowanceCount<T>>::mutate(id, |count| *count = count.saturating_add(1));
+            Self::reconcile_recount(id, &<Allowances<T>>::hashed_key_for((id, owner, spender)), |_, allowances| {
+                *allowances = allowances.saturating_add(1)
+            });
+        } else if !old_amount.is_zero() && amount.is_zero() {
+            <AllowanceCount<T>>::mutate(id, |count| {
+                debug_assert!(*count > 0, "allowance count out of sync with allowances");
+                *count = count.saturating_sub(1);
+            });
+            Self::reconcile_recount(id, &<Allowances<T>>::hashed_key_for((id, owner, spender)), |_, allowances| {
+                *allowances = allowances.saturating_sub(1)
+            });
+        }
+
+        if amount.is_zero() {
+            <Allowances<T>>::remove((id, owner, spender));
+            <SpenderAllowances<T>>::remove(spender, (id, owner));
+            <OwnerAllowances<T>>::remove((id, owner), spender);
+        } else {
+            <Allowances<T>>::insert((id, owner, spender), amount);
+            <SpenderAllowances<T>>::insert(spender, (id, owner), true);
+            <OwnerAllowances<T>>::insert((id, owner), spender, true);
+        }
+    }
+
+    pub fn inner_issue(
+        owner: &T::AccountId,
+        initial_supply: T::TokenBalance,
+        info: &AssetInfo,
+    ) -> T::AssetId {
+        let id = Self::next_asset_id();
+        <NextAssetId<T>>::mutate(|id| *id += One::one());
+
+        Self::set_balance(&id, owner, initial_supply);
+        <TotalSupply<T>>::insert(id, initial_supply);
+        <InitialSupply<T>>::insert(id, initial_supply);
+        <AssetInfos<T>>::insert(id, info);
+        <Owners<T>>::insert(id, owner);
+
+        Self::deposit_event(RawEvent::Issued(id, owner.clone(), initial_supply));
+
+        id
+    }
+
+    pub fn inner_launch_asset(
+        owner: &T::AccountId,
+        info: &AssetInfo,
+        distribution: Vec<(T::AccountId, T::TokenBalance)>,
+        fee_bps: u16,
+        collector: Option<T::AccountId>,
+        max_supply: Option<T::TokenBalance>,
+    ) -> DispatchResult {
+        ensure!(fee_bps <= MAX_BPS, Error::<T>::InvalidFee);
+        let mut total: T::TokenBalance = Zero::zero();
+        for (_, amount) in distribution.iter() {
+            ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+            total = total.checked_add(amount).ok_or(Error::<T>::SupplyCapExceeded)?;
+        }
+        if let Some(max) = max_supply {
+            ensure!(total <= max, Error::<T>::SupplyCapExceeded);
+        }
+
+        let id = Self::inner_issue(owner, Zero::zero(), info);
+        for (to, amount) in distribution {
+            Self::inner_mint(&id, &to, amount)?;
+        }
+        <InitialSupply<T>>::insert(id, total);
+
+        <TransferFees<T>>::insert(id, fee_bps);
+        Self::deposit_event(RawEvent::TransferFeeSet(id, fee_bps));
+        if let Some(collector) = collector {
+            <FeeCollectors<T>>::insert(id, &collector);
+            Self::deposit_event(RawEvent::FeeCollectorSet(id, collector));
+        }
+        <MaxSupply<T>>::mutate(id, |max| *max = max_supply);
+        Self::deposit_event(RawEvent::MaxSupplySet(id, max_supply));
+
+        Ok(())
+    }
+
+    /// Whether `id` has ever been allocated to an asset. Ids are handed out in order and never
+    /// reused, so this stays true even once the asset no longer exists.
+    pub fn asset_id_ever_used(id: &T::AssetId) -> bool {
+        *id < Self::next_asset_id()
+    }
+
+    pub fn asset_info(id: &T::AssetId) -> Option<AssetInfo> {
+        <AssetInfos<T>>::get(id)
+    }
+
+    pub fn asset_config(
+        id: &T::AssetId,
+    ) -> Option<AssetConfig<T::AccountId, T::TokenBalance, T::BlockNumber>> {
+        let owner = Self::owner(id)?;
+
+        Some(AssetConfig {
+            fee_collector: Self::fee_collector(id).unwrap_or_else(|| owner.clone()),
+            owner,
+            fee_bps: Self::transfer_fee(id),
+            min_hold_blocks: Self::min_hold_blocks(id),
+            aggregate_transfer_events: <AggregateTransferEvents<T>>::get(id),
+            allow_delegation: Self::allow_delegation(id),
+            restrict_destinations: Self::restrict_destinations(id),
+            max_supply: Self::max_supply(id),
+            mint_frozen: Self::is_mint_frozen(id),
+            min_balance: Self::min_balance(id),
+            min_precision: Self::min_precision(id),
+            asset_frozen: Self::is_asset_frozen(id),
+            freeze_renounced: Self::is_freeze_renounced(id),
+            display_decimals: Self::display_decimals(id),
+            wound_down: Self::is_wound_down(id),
+            event_verbosity: Self::event_verbosity(id),
+            enumerable: Self::is_enumerable(id),
+            trading_start_block: Self::trading_start_block(id),
+            max_allowance: Self::max_allowance(id),
+            large_transfer_confirmation: Self::large_transfer_confirmation(id),
+            wash_trade_protection: Self::wash_trade_protection(id),
+        })
+    }
+
+    /// How much more can be minted: `None` if unlimited or the asset doesn't exist, zero if
+    /// minting is frozen.
+    pub fn remaining_mintable(id: &T::AssetId) -> Option<T::TokenBalance> {
+        Self::asset_info(id)?;
+        if Self::is_mint_frozen(id) {
+            return Some(Zero::zero());
+        }
+
+        Self::max_supply(id).map(|max| max.saturating_sub(Self::total_supply(id)))
+    }
+
+    /// `amount` formatted with the asset's decimals, for debug output.
+    pub fn formatted_balance(id: &T::AssetId, amount: T::TokenBalance) -> FormattedBalance {
+        FormattedBalance {
+            amount: amount.saturated_into(),
+            decimals: Self::asset_info(id).map(|info| info.decimals).unwrap_or_default(),
+        }
+    }
+
+    fn ensure_owner(id: &T::AssetId, who: &T::AccountId) -> DispatchResult {
+        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+        ensure!(Self::owner(id).as_ref() == Some(who), Error::<T>::NotOwner);
+
+        Ok(())
+    }
+
+    fn ensure_freezer(id: &T::AssetId, who: &T::AccountId) -> DispatchResult {
+        Self::ensure_owner(id, who)?;
+        ensure!(!Self::is_freeze_renounced(id), Error::<T>::FreezeRenounced);
+
+        Ok(())
+    }
+
+    fn ensure_root_or_owner(origin: T::Origin, id: &T::AssetId) -> DispatchResult {
+        if ensure_root(origin.clone()).is_ok() {
+            return Ok(());
+        }
+
+        let who = ensure_signed(origin)?;
+        Self::ensure_owner(id, &who)
+    }
+
+    pub fn inner_transfer(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        target: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> DispatchResult {
+        let new_balance = Self::ensure_can_transfer(id, owner, target, amount)?;
+
+        if *target == T::BurnAddress::get() {
+            Self::inner_burn(id, owner, amount)?;
+            <BurnedToAddress<T>>::mutate(id, |burned| *burned = burned.saturating_add(amount));
+
+            return Ok(());
+        }
+
+        Self::set_balance(id, owner, new_balance);
+        Self::credit_transfer(id, owner, target, amount);
+
+        Ok(())
+    }
+
+    pub fn inner_airdrop_equal(
+        id: &T::AssetId,
+        from: &T::AccountId,
+        recipients: &[T::AccountId],
+        amount_each: T::TokenBalance,
+    ) -> DispatchResult {
+        let count: T::TokenBalance = (recipients.len() as u32).into();
+        let total = amount_each.checked_mul(&count).ok_or(Error::<T>::BalanceLow)?;
+        for (index, target) in recipients.iter().enumerate() {
+            ensure!(target != from && !recipients[..index].contains(target), Error::<T>::InvalidRecipient);
+            Self::ensure_can_transfer(id, from, target, amount_each)?;
+        }
+        let new_balance = Self::balance_of(id, from).checked_sub(&total).ok_or(Error::<T>::BalanceLow)?;
+        ensure!(new_balance >= Self::locked_balance(id, from), Error::<T>::BalanceLocked);
+
+        for target in recipients {
+            Self::inner_transfer(id, from, target, amount_each)?;
+        }
+
+        Ok(())
+    }
+
+    /// Credit `target` with `amount` already taken from `from`, less the transfer fee.
+    fn credit_transfer(id: &T::AssetId, from: &T::AccountId, target: &T::AccountId, amount: T::TokenBalance) {
+        let received = Self::charge_transfer_fee(id, from, amount);
+        Self::set_balance(id, target, Self::balance_of(id, target).saturating_add(received));
+        Self::note_acquired(id, target);
+        if T::TrackCounterparties::get() {
+            <Counterparties<T>>::insert((id, from, target), true);
+        }
+
+        Self::deposit_transferred(id, from, target, received);
+    }
+
+    pub fn inner_emergency_withdraw(id: &T::AssetId, who: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+        ensure!(Self::is_asset_frozen(id), Error::<T>::AssetNotFrozen);
+        ensure!(Self::recovery_address((id, who)).as_ref() == Some(to), Error::<T>::NotRecoveryAddress);
+
+        let locked = Self::locked_balance(id, who);
+        let amount = Self::balance_of(id, who).saturating_sub(locked);
+        ensure!(!amount.is_zero(), Error::<T>::BalanceZero);
+        // A frozen asset can't be sent with `initiate_transfer`, so no large transfer threshold.
+        let new_balance = Self::ensure_can_transfer_while_frozen(id, who, to, amount)?;
+
+        Self::set_balance(id, who, new_balance);
+        Self::credit_transfer(id, who, to, amount);
+
+        Ok(())
+    }
+
+    pub fn inner_recompute_counts(id: &T::AssetId, max: u32) {
+        let balances_prefix = <Balances<T>>::final_prefix();
+        let allowances_prefix = <Allowances<T>>::final_prefix();
+        let (mut holders, mut allowances, mut cursor) =
+            Self::count_recomputation(id).unwrap_or_else(|| (0, 0, balances_prefix.to_vec()));
+
+        let mut visited = 0;
+        let done = loop {
+            if visited == max {
+                break false;
+            }
+            let counting_balances = !cursor.starts_with(&allowances_prefix);
+            let prefix = if counting_balances { &balances_prefix } else { &allowances_prefix };
+            let key = match sp_io::storage::next_key(&cursor) {
+                Some(key) if key.starts_with(prefix) => key,
+                _ if counting_balances => {
+                    cursor = allowances_prefix.to_vec();
+                    continue;
+                }
+                _ => break true,
+            };
+
+            // Keys are the prefix, a 16 byte hash and the encoded key, which starts with the asset.
+            let asset = key.get(prefix.len() + 16..).and_then(|mut raw| T::AssetId::decode(&mut raw).ok());
+            let counted = asset.as_ref() == Some(id)
+                && frame_support::storage::unhashed::get::<T::TokenBalance>(&key)
+                    .map_or(false, |amount| !amount.is_zero());
+            if counted && counting_balances {
+                holders += 1;
+            } else if counted {
+                allowances += 1;
+            }
+            visited += 1;
+            cursor = key;
+        };
+
+        if done {
+            <HolderCount<T>>::insert(id, holders);
+            <AllowanceCount<T>>::insert(id, allowances);
+            <CountRecomputations<T>>::remove(id);
+
+            Self::deposit_event(RawEvent::CountsRecomputed(*id, holders, allowances));
+        } else {
+            <CountRecomputations<T>>::insert(id, (holders, allowances, cursor));
+        }
+    }
+
+    /// Apply a change of the holder or allowance count of `id`, from writing the entry stored
+    /// under `key`, to a recount in progress that has already visited that entry.
+    fn reconcile_recount(id: &T::AssetId, key: &[u8], update: impl FnOnce(&mut u32, &mut u32)) {
+        let (mut holders, mut allowances, cursor) = match Self::count_recomputation(id) {
+            Some(recount) => recount,
+            None => return,
+        };
+
+        let allowances_prefix = <Allowances<T>>::final_prefix();
+        let visited = if key.starts_with(&allowances_prefix) {
+            cursor.starts_with(&allowances_prefix) && key <= &cursor[..]
+        } else {
+            cursor.starts_with(&allowances_prefix) || key <= &cursor[..]
+        };
+        if visited {
+            update(&mut holders, &mut allowances);
+            <CountRecomputations<T>>::insert(id, (holders, allowances, cursor));
+        }
+    }
+
+    pub fn inner_pay(from: &T::AccountId, to: &T::AccountId, native_amount: BalanceOf<T>) -> DispatchResult {
+        let id = Self::preferred_asset(to).ok_or(Error::<T>::NoPreferredAsset)?;
+        let rate = Self::exchange_rate(id).ok_or(Error::<T>::NoExchangeRate)?;
+        let amount = Self::from_native(&id, native_amount)
+            .and_then(|amount| amount.checked_mul(&rate))
+            .ok_or(Error::<T>::ConversionFailed)?;
+
+        Self::inner_transfer(&id, from, to, amount)?;
+
+        Self::deposit_event(RawEvent::Paid(from.clone(), to.clone(), id, native_amount, amount));
+
+        Ok(())
+    }
+
+    pub fn inner_merge_accounts(id: &T::AssetId, from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+        ensure!(Self::merge_acceptance((id, to)).as_ref() == Some(from), Error::<T>::MergeNotAccepted);
+
+        let granted: Vec<(T::AccountId, T::TokenBalance, T::TokenBalance)> =
+            <OwnerAllowances<T>>::iter_prefix((id, from))
+                .map(|(spender, _)| {
+                    let amount = Self::allowances(id, from, &spender);
+                    let merged = Self::allowances(id, to, &spender).saturating_add(amount);
+                    (spender, amount, merged)
+                })
+                .collect();
+        ensure!(granted.is_empty() || Self::allow_delegation(id), Error::<T>::DelegationDisabled);
+        for (_, _, merged) in granted.iter() {
+            Self::ensure_allowance_within_cap(id, *merged)?;
+        }
+
+        let balance = Self::balance_of(id, from);
+        if !balance.is_zero() {
+            Self::inner_transfer(id, from, to, balance)?;
+        }
+        <MergeAcceptances<T>>::remove((id, to));
+
+        for (spender, _, merged) in granted {
+            Self::set_allowance(id, from, &spender, Zero::zero());
+            Self::set_allowance(id, to, &spender, merged);
+            // A standing approval `to` already gave the spender takes precedence.
+            if let Some(approval) = <StandingApprovals<T>>::take((id, from, &spender)) {
+                if Self::standing_approval((id, to, &spender)).is_none() {
+                    <StandingApprovals<T>>::insert((id, to, &spender), approval);
+                }
+            }
+
+            Self::deposit_event(RawEvent::Approval(*id, from.clone(), spender.clone(), Zero::zero()));
+            Self::deposit_event(RawEvent::Approval(*id, to.clone(), spender, merged));
+        }
+
+        Ok(())
+    }
+
+    pub fn inner_initiate_transfer(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        target: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> DispatchResult {
+        let (_, window) = Self::large_transfer_confirmation(id).ok_or(Error::<T>::ConfirmationNotRequired)?;
+        ensure!(Self::pending_transfer((id, owner)).is_none(), Error::<T>::TransferPending);
+        let new_balance = Self::ensure_can_initiate_transfer(id, owner, target, amount)?;
+
+        let deadline = <frame_system::Module<T>>::block_number().saturating_add(window);
+        Self::set_balance(id, owner, new_balance);
+        <PendingTransfers<T>>::insert((id, owner), PendingTransfer {
+            target: target.clone(),
+            amount,
+            deadline,
+        });
+
+        Self::deposit_event(RawEvent::TransferInitiated(*id, owner.clone(), target.clone(), amount, deadline));
+
+        Ok(())
+    }
+
+    pub fn inner_confirm_transfer(id: &T::AssetId, owner: &T::AccountId) -> DispatchResult {
+        let pending = Self::pending_transfer((id, owner)).ok_or(Error::<T>::NoPendingTransfer)?;
+
+        if <frame_system::Module<T>>::block_number() > pending.deadline {
+            <PendingTransfers<T>>::remove((id, owner));
+            Self::set_balance(id, owner, Self::balance_of(id, owner).saturating_add(pending.amount));
+            Self::deposit_event(RawEvent::PendingTransferExpired(*id, owner.clone(), pending.amount));
+
+            return Ok(());
+        }
+
+        ensure!(!Self::is_asset_frozen(id), Error::<T>::AssetFrozen);
+        ensure!(!Self::is_frozen((id, owner)), Error::<T>::AccountFrozen);
+
+        <PendingTransfers<T>>::remove((id, owner));
+        if pending.target == T::BurnAddress::get() {
+            <TotalSupply<T>>::mutate(id, |supply| *supply = supply.saturating_sub(pending.amount));
+            <BurnedToAddress<T>>::mutate(id, |burned| *burned = burned.saturating_add(pending.amount));
+            Self::deposit_event(RawEvent::Burned(*id, owner.clone(), pending.amount));
+        } else {
+            Self::credit_transfer(id, owner, &pending.target, pending.amount);
+        }
+
+        Ok(())
+    }
+
+    pub fn inner_cancel_transfer(id: &T::AssetId, owner: &T::AccountId) -> DispatchResult {
+        let pending = Self::pending_transfer((id, owner)).ok_or(Error::<T>::NoPendingTransfer)?;
+
+        <PendingTransfers<T>>::remove((id, owner));
+        Self::set_balance(id, owner, Self::balance_of(id, owner).saturating_add(pending.amount));
+
+        Self::deposit_event(RawEvent::PendingTransferCancelled(*id, owner.clone(), pending.amount));
+
+        Ok(())
+    }
+
+    pub fn inner_transfer_signed(
+        id: &T::AssetId,
+        from: &T::AccountId,
+        target: &T::AccountId,
+        amount: T::TokenBalance,
+        nonce: u32,
+        signature: &T::Signature,
+    ) -> DispatchResult {
+        ensure!(nonce == Self::transfer_nonce((id, from)), Error::<T>::BadNonce);
+
+        let message = (id, target, amount, nonce).encode();
+        ensure!(signature.verify(&message[..], from), Error::<T>::BadSignature);
+
+        Self::inner_transfer(id, from, target, amount)?;
+
+        <TransferNonce<T>>::insert((id, from), nonce.saturating_add(1));
+
+        Ok(())
+    }
+
+    pub fn inner_transfer_no_dust(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        target: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> DispatchResult {
+        let min_balance = Self::min_balance(id);
+        if !min_balance.is_zero() {
+            let received = amount.saturating_sub(Self::transfer_fee_of(id, amount));
+            ensure!(
+                Self::balance_of(id, target).saturating_add(received) >= min_balance,
+                Error::<T>::BalanceTooLow
+            );
+        }
+
+        Self::inner_transfer(id, owner, target, amount)
+    }
+
+    /// Check that a delegated spend leaves the owner with at least the asset's minimum balance.
+    fn ensure_keeps_min_balance(id: &T::AssetId, new_balance: T::TokenBalance) -> Result<(), Error<T>> {
+        ensure!(new_balance >= Self::min_balance(id), Error::<T>::BalanceTooLow);
+
+        Ok(())
+    }
+
+    /// Check that `from` may send `amount` to `to`, returning its balance after the transfer.
+    fn ensure_can_transfer(
+        id: &T::AssetId,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> Result<T::TokenBalance, Error<T>> {
+        Self::ensure_confirmation_not_required(id, amount)?;
+
+        Self::ensure_can_initiate_transfer(id, from, to, amount)
+    }
+
+    /// Every check of `ensure_can_transfer` but the large transfer threshold, for transfers that
+    /// only complete once confirmed.
+    fn ensure_can_initiate_transfer(
+        id: &T::AssetId,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> Result<T::TokenBalance, Error<T>> {
+        ensure!(!Self::is_asset_frozen(id), Error::<T>::AssetFrozen);
+
+        Self::ensure_can_transfer_while_frozen(id, from, to, amount)
+    }
+
+    /// Amounts above the asset's large transfer threshold must be sent with `initiate_transfer`.
+    fn ensure_confirmation_not_required(id: &T::AssetId, amount: T::TokenBalance) -> Result<(), Error<T>> {
+        if let Some((threshold, _)) = Self::large_transfer_confirmation(id) {
+            ensure!(amount <= threshold, Error::<T>::ConfirmationRequired);
+        }
+
+        Ok(())
+    }
+
+    /// Every check of `ensure_can_initiate_transfer` but the asset freeze.
+    fn ensure_can_transfer_while_frozen(
+        id: &T::AssetId,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> Result<T::TokenBalance, Error<T>> {
+        let info = Self::asset_info(id).ok_or(Error::<T>::AssetNotExists)?;
+        Self::ensure_precision(id, &info, amount)?;
+
+        let from_balance = Self::balance_of(id, from);
+        ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+        Self::ensure_moves_raw_balance(id, amount)?;
+        ensure!(from_balance >= amount, Error::<T>::BalanceLow);
+        ensure!(Self::transfer_gate((id, from)), Error::<T>::TransfersDisabled);
+        ensure!(!Self::is_frozen((id, from)), Error::<T>::AccountFrozen);
+        ensure!(
+            !Self::is_wound_down(id) || *to == T::BurnAddress::get(),
+            Error::<T>::AssetWoundDown
+        );
+        ensure!(
+            <frame_system::Module<T>>::block_number() >= Self::trading_start_block(id),
+            Error::<T>::TradingNotStarted
+        );
+        Self::ensure_hold_period_passed(id, from)?;
+        Self::ensure_destination_allowed(id, from, to)?;
+        ensure!(
+            !Self::wash_trade_protection(id) || (from != to && !Self::are_related((id, from, to))),
+            Error::<T>::WashTradeBlocked
+        );
+
+        let new_balance = from_balance.saturating_sub(amount);
+        ensure!(new_balance >= Self::locked_balance(id, from), Error::<T>::BalanceLocked);
+
+        Ok(new_balance)
+    }
+
+    fn ensure_precision(id: &T::AssetId, info: &AssetInfo, amount: T::TokenBalance) -> Result<(), Error<T>> {
+        let precision = match Self::min_precision(id) {
+            Some(precision) => precision,
+            None => return Ok(()),
+        };
+
+        let lot = 10u128.checked_pow(info.decimals.saturating_sub(precision).into());
+        let amount: u128 = amount.saturated_into();
+        ensure!(lot.map_or(false, |lot| amount % lot == 0), Error::<T>::InvalidPrecision);
+
+        Ok(())
+    }
+
+    /// Transfers from or to the owner are exempt from destination restrictions.
+    fn ensure_destination_allowed(
+        id: &T::AssetId,
+        from: &T::AccountId,
+        to: &T::AccountId,
+    ) -> Result<(), Error<T>> {
+        if !Self::restrict_destinations(id) {
+            return Ok(());
+        }
+
+        let owner = Self::owner(id);
+        let exempt = owner.as_ref() == Some(from) || owner.as_ref() == Some(to);
+        ensure!(exempt || Self::is_allowed_destination((id, to)), Error::<T>::DestinationNotAllowed);
+
+        Ok(())
+    }
+
+    /// Dry-run of `inner_transfer`, returning the sender's balance after the transfer.
+    pub fn simulate_transfer(
+        id: &T::AssetId,
+        from: &T::AccountId,
+        target: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> Result<T::TokenBalance, Error<T>> {
+        Self::ensure_can_transfer(id, from, target, amount)
+    }
+
+    /// Dry-run of `inner_transfer_from`, returning the spender's allowance after the transfer.
+    pub fn simulate_transfer_from(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        target: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> Result<T::TokenBalance, Error<T>> {
+        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+        ensure!(owner == spender || Self::allow_delegation(id), Error::<T>::DelegationDisabled);
+
+        let (allowance, _) = Self::replenished_allowance(id, owner, spender);
+        let remaining = allowance
+            .checked_sub(&amount)
+            .ok_or(Error::<T>::AllowanceLow)?;
+        let new_balance = Self::ensure_can_transfer(id, owner, target, amount)?;
+        Self::ensure_keeps_min_balance(id, new_balance)?;
+
+        Ok(remaining)
+    }
+
+    /// For each `(owner, amount)`, whether `spender` is allowed to and `owner` holds enough to
+    /// cover `amount`.
+    pub fn allowances_sufficient(
+        id: &T::AssetId,
+        spender: &T::AccountId,
+        requests: Vec<(T::AccountId, T::TokenBalance)>,
+    ) -> Vec<bool> {
+        requests
+            .into_iter()
+            .map(|(owner, amount)| {
+                let (allowance, _) = Self::replenished_allowance(id, &owner, spender);
+                allowance >= amount && Self::balance_of(id, &owner) >= amount
+            })
+            .collect()
+    }
+
+    /// The account receiving transfer fees of an asset, the owner unless set otherwise.
+    pub fn fee_collector(id: &T::AssetId) -> Option<T::AccountId> {
+        <FeeCollectors<T>>::get(id).or_else(|| Self::owner(id))
+    }
+
+    /// Credit the fee collector with the transfer fee on `amount` sent by `from`, returning
+    /// what is left for the recipient.
+    fn charge_transfer_fee(
+        id: &T::AssetId,
+        from: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> T::TokenBalance {
+        let fee = Self::transfer_fee_of(id, amount);
+        if fee.is_zero() {
+            return amount;
+        }
+
+        match Self::fee_collector(id) {
+            Some(collector) => {
+                Self::set_balance(id, &collector, Self::balance_of(id, &collector).saturating_add(fee));
+                Self::deposit_transferred(id, from, &collector, fee);
+
+                amount.saturating_sub(fee)
+            }
+            None => amount,
+        }
+    }
+
+    /// Withdraw a transaction fee of `fee` native units from `who` in the fee asset.
+    ///
+    /// Together with `correct_and_deposit_fee` this is the fee-asset side of an
+    /// `OnChargeTransaction` implementation: the returned asset and amount are the liquidity held
+    /// until the fee is corrected after dispatch.
+    pub fn withdraw_fee(
+        who: &T::AccountId,
+        fee: BalanceOf<T>,
+    ) -> Result<Option<(T::AssetId, T::TokenBalance)>, TransactionValidityError> {
+        if fee.is_zero() {
+            return Ok(None);
+        }
+
+        let (id, rate) = Self::fee_asset().ok_or(InvalidTransaction::Payment)?;
+        let amount = Self::native_to_fee_asset(&id, fee, rate).ok_or(InvalidTransaction::Payment)?;
+        let new_balance = Self::balance_of(&id, who)
+            .checked_sub(&amount)
+            .ok_or(InvalidTransaction::Payment)?;
+        if new_balance < Self::locked_balance(&id, who)
+            || Self::is_asset_frozen(&id)
+            || Self::is_frozen((id, who))
+        {
+            return Err(InvalidTransaction::Payment.into());
+        }
+
+        Self::set_balance(&id, who, new_balance);
+
+        Ok(Some((id, amount)))
+    }
+
+    /// Settle a fee withdrawn by `withdraw_fee`: the asset equivalent of `corrected_fee` goes to
+    /// the asset's fee collector and the rest of `already_withdrawn` is refunded to `who`.
+    pub fn correct_and_deposit_fee(
+        who: &T::AccountId,
+        corrected_fee: BalanceOf<T>,
+        already_withdrawn: Option<(T::AssetId, T::TokenBalance)>,
+    ) -> Result<(), TransactionValidityError> {
+        let (id, withdrawn) = match already_withdrawn {
+            Some(withdrawn) => withdrawn,
+            None => return Ok(()),
+        };
+
+        // If the fee asset changed during dispatch, keep the whole withdrawn amount.
+        let charged = match Self::fee_asset() {
+            Some((fee_id, rate)) if fee_id == id => Self::native_to_fee_asset(&id, corrected_fee, rate)
+                .ok_or(InvalidTransaction::Payment)?
+                .min(withdrawn),
+            _ => withdrawn,
+        };
+        let refund = withdrawn.saturating_sub(charged);
+
+        Self::set_balance(&id, who, Self::balance_of(&id, who).saturating_add(refund));
+        match Self::fee_collector(&id) {
+            Some(collector) => {
+                Self::set_balance(&id, &collector, Self::balance_of(&id, &collector).saturating_add(charged));
+            }
+            None => <TotalSupply<T>>::mutate(id, |supply| *supply = supply.saturating_sub(charged)),
+        }
+
+        Self::deposit_event(RawEvent::TransactionFeePaid(id, who.clone(), charged));
+
+        Ok(())
+    }
+
+    fn native_to_fee_asset(id: &T::AssetId, fee: BalanceOf<T>, rate: T::TokenBalance) -> Option<T::TokenBalance> {
+        Self::from_native(id, fee)?.checked_mul(&rate)
+    }
+
+    /// `(fee_bps, burn_bps, collector)` of an asset, `None` if it doesn't exist. Transfers don't
+    /// burn any part of the amount, so `burn_bps` is always zero.
+    pub fn fee_config(id: &T::AssetId) -> Option<(u16, u16, T::AccountId)> {
+        Self::asset_info(id)?;
+        let collector = Self::fee_collector(id)?;
+
+        Some((Self::transfer_fee(id), 0, collector))
+    }
+
+    /// `(net_to_recipient, fee, burned)` of a transfer of `amount`. The fee is rounded down and
+    /// only charged once the asset has a fee collector. No asset burns part of a transfer, so
+    /// `burned` is always zero.
+    pub fn quote_transfer(
+        id: &T::AssetId,
+        amount: T::TokenBalance,
+    ) -> (T::TokenBalance, T::TokenBalance, T::TokenBalance) {
+        let fee = match Self::fee_collector(id) {
+            Some(_) => Self::transfer_fee_of(id, amount),
+            None => Zero::zero(),
+        };
+
+        (amount.saturating_sub(fee), fee, Zero::zero())
+    }
+
+    /// Fee charged on a transfer of `amount`.
+    pub fn transfer_fee_of(id: &T::AssetId, amount: T::TokenBalance) -> T::TokenBalance {
+        Self::bps_of(amount, Self::transfer_fee(id))
+    }
+
+    /// `bps` basis points of `amount`, rounded down.
+    fn bps_of(amount: T::TokenBalance, bps: u16) -> T::TokenBalance {
+        let amount: u128 = amount.saturated_into();
+
+        (amount.saturating_mul(bps.into()) / u128::from(MAX_BPS)).saturated_into()
+    }
+
+    fn ensure_hold_period_passed(id: &T::AssetId, who: &T::AccountId) -> Result<(), Error<T>> {
+        let hold = Self::min_hold_blocks(id);
+        if hold.is_zero() {
+            return Ok(());
+        }
+
+        if let Some(acquired_at) = Self::acquired_at((id, who)) {
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(now >= acquired_at.saturating_add(hold), Error::<T>::HoldPeriodActive);
+        }
+
+        Ok(())
+    }
+
+    fn note_acquired(id: &T::AssetId, who: &T::AccountId) {
+        if !Self::min_hold_blocks(id).is_zero() {
+            <AcquiredAt<T>>::insert((id, who), <frame_system::Module<T>>::block_number());
+        }
+    }
+
+    pub fn inner_freeze(id: &T::AssetId, who: &T::AccountId) {
+        <Frozen<T>>::insert((id, who), true);
+
+        Self::deposit_event(RawEvent::Frozen(*id, who.clone()));
+    }
+
+    pub fn inner_set_transfer_gate(id: &T::AssetId, who: &T::AccountId, enabled: bool) {
+        if enabled {
+            <TransferGate<T>>::remove((id, who));
+        } else {
+            <TransferGate<T>>::insert((id, who), false);
+        }
+
+        Self::deposit_event(RawEvent::TransferGateSet(*id, who.clone(), enabled));
+    }
+
+    fn deposit_transferred(
+        id: &T::AssetId,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: T::TokenBalance,
+    ) {
+        <RecentTransfers<T>>::mutate(id, |transfers| {
+            if transfers.len() >= MAX_RECENT_TRANSFERS {
+                transfers.remove(0);
+            }
+            transfers.push((from.clone(), to.clone(), amount, <frame_system::Module<T>>::block_number()));
+        });
+        Self::note_volume(id, amount);
+
+        if <AggregateTransferEvents<T>>::get(id) {
+            <TransferSummaries<T>>::mutate(id, |(count, volume)| {
+                *count = count.saturating_add(1);
+                *volume = volume.saturating_add(amount);
+            });
+        } else {
+            match Self::event_verbosity(id) {
+                EventVerbosity::Full => Self::deposit_event(RawEvent::Transferred(
+                    *id,
+                    from.clone(),
+                    to.clone(),
+                    amount,
+                )),
+                EventVerbosity::MinimalAmountOnly => Self::deposit_event(RawEvent::TransferredAmount(*id, amount)),
+                EventVerbosity::None => {}
+            }
+        }
+    }
+
+    fn note_volume(id: &T::AssetId, amount: T::TokenBalance) {
+        let now = <frame_system::Module<T>>::block_number();
+        <BlockVolumes<T>>::mutate(id, |volumes| match volumes.last_mut() {
+            Some((block, volume)) if *block == now => *volume = volume.saturating_add(amount),
+            _ => {
+                if volumes.len() >= MAX_VOLUME_BLOCKS {
+                    volumes.remove(0);
+                }
+                volumes.push((now, amount));
+            }
+        });
+    }
+
+    /// Volume transferred in the last `n` blocks, including the current one. Only the last
+    /// `MAX_VOLUME_BLOCKS` blocks with transfers are kept.
+    pub fn volume_last_n_blocks(id: &T::AssetId, n: u32) -> T::TokenBalance {
+        let now = <frame_system::Module<T>>::block_number();
+        let n: T::BlockNumber = n.into();
+
+        Self::block_volumes(id)
+            .iter()
+            .filter(|(block, _)| now.saturating_sub(*block) < n)
+            .fold(Zero::zero(), |total: T::TokenBalance, (_, volume)| total.saturating_add(*volume))
+    }
+
+    pub fn inner_transfer_from(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        target: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> DispatchResult {
+        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+        ensure!(owner == spender || Self::allow_delegation(id), Error::<T>::DelegationDisabled);
+
+        let (allowance, replenished) = Self::replenished_allowance(id, owner, spender);
+        let new_balance = allowance
+            .checked_sub(&amount)
+            .ok_or(Error::<T>::AllowanceLow)?;
+        let new_owner_balance = Self::balance_of(id, owner)
+            .checked_sub(&amount)
+            .ok_or(Error::<T>::BalanceLow)?;
+        Self::ensure_keeps_min_balance(id, new_owner_balance)?;
+
+        Self::inner_transfer(&id, &owner, &target, amount)?;
+
+        Self::set_allowance(id, owner, spender, new_balance);
+        if let Some(approval) = replenished {
+            <StandingApprovals<T>>::insert((id, owner, spender), approval);
+        }
+
+        Ok(())
+    }
+
+    pub fn inner_reserve_allowance(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> DispatchResult {
         ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+        ensure!(owner == spender || Self::allow_delegation(id), Error::<T>::DelegationDisabled);
 
-        let new_balance = <Balances<T>>::get((id, owner))
+        let (allowance, replenished) = Self::replenished_allowance(id, owner, spender);
+        let new_allowance = allowance
             .checked_sub(&amount)
-            .ok_or(Error::<T>::BalanceLow)?;
+            .ok_or(Error::<T>::AllowanceLow)?;
+        let new_balance = Self::ensure_can_transfer(id, owner, spender, amount)?;
+
+        Self::set_allowance(id, owner, spender, new_allowance);
+        if let Some(approval) = replenished {
+            <StandingApprovals<T>>::insert((id, owner, spender), approval);
+        }
+        Self::reserve(id, owner, spender, new_balance, amount);
+
+        Ok(())
+    }
+
+    /// Move `amount` of `owner`'s balance, leaving `new_balance`, into reserve for `spender`.
+    fn reserve(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        new_balance: T::TokenBalance,
+        amount: T::TokenBalance,
+    ) {
+        Self::set_balance(id, owner, new_balance);
+        <Reserved<T>>::mutate((id, owner, spender), |reserved| *reserved = reserved.saturating_add(amount));
+
+        Self::deposit_event(RawEvent::AllowanceReserved(*id, owner.clone(), spender.clone(), amount));
+    }
+
+    pub fn inner_release_reserved(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        target: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> DispatchResult {
+        ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+        let remaining = Self::reserved((id, owner, spender))
+            .checked_sub(&amount)
+            .ok_or(Error::<T>::ReserveLow)?;
+        if target != owner {
+            Self::ensure_can_release_to(id, owner, target, amount)?;
+        }
+
+        if remaining.is_zero() {
+            <Reserved<T>>::remove((id, owner, spender));
+        } else {
+            <Reserved<T>>::insert((id, owner, spender), remaining);
+        }
+        if target == owner {
+            Self::set_balance(id, owner, Self::balance_of(id, owner).saturating_add(amount));
+        } else if *target == T::BurnAddress::get() {
+            <TotalSupply<T>>::mutate(id, |supply| *supply = supply.saturating_sub(amount));
+            <BurnedToAddress<T>>::mutate(id, |burned| *burned = burned.saturating_add(amount));
+            Self::deposit_event(RawEvent::Burned(*id, owner.clone(), amount));
+        } else {
+            Self::credit_transfer(id, owner, target, amount);
+        }
+
+        Self::deposit_event(RawEvent::ReserveReleased(
+            *id,
+            owner.clone(),
+            spender.clone(),
+            target.clone(),
+            amount,
+        ));
+
+        Ok(())
+    }
+
+    /// The checks `ensure_can_transfer` applies to the asset, the amount and the destination, for
+    /// `amount` of reserved tokens of `owner` paid out to `target`.
+    fn ensure_can_release_to(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        target: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> DispatchResult {
+        Self::ensure_confirmation_not_required(id, amount)?;
+        ensure!(!Self::is_asset_frozen(id), Error::<T>::AssetFrozen);
+        ensure!(!Self::is_frozen((id, owner)), Error::<T>::AccountFrozen);
+        ensure!(
+            !Self::is_wound_down(id) || *target == T::BurnAddress::get(),
+            Error::<T>::AssetWoundDown
+        );
+        ensure!(
+            <frame_system::Module<T>>::block_number() >= Self::trading_start_block(id),
+            Error::<T>::TradingNotStarted
+        );
+        Self::ensure_destination_allowed(id, owner, target)?;
+        ensure!(
+            !Self::wash_trade_protection(id) || !Self::are_related((id, owner, target)),
+            Error::<T>::WashTradeBlocked
+        );
+
+        Ok(())
+    }
+
+    /// The allowance available to `spender`, topped up if a standing approval's period has
+    /// elapsed, along with the updated standing approval to store if it was.
+    fn replenished_allowance(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+    ) -> (T::TokenBalance, Option<StandingApproval<T::TokenBalance, T::BlockNumber>>) {
+        let allowance = <Allowances<T>>::get((id, owner, spender));
+
+        match Self::standing_approval((id, owner, spender)) {
+            Some(mut approval) => {
+                let now = <frame_system::Module<T>>::block_number();
+                if now >= approval.last_replenish.saturating_add(approval.period) {
+                    approval.last_replenish = now;
+                    (approval.replenish_to, Some(approval))
+                } else {
+                    (allowance, None)
+                }
+            }
+            None => (allowance, None),
+        }
+    }
+
+    pub fn inner_set_standing_approval(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        replenish_to: T::TokenBalance,
+        period: T::BlockNumber,
+    ) -> DispatchResult {
+        ensure!(!period.is_zero(), Error::<T>::PeriodZero);
+
+        Self::inner_approve(id, owner, spender, replenish_to)?;
+
+        <StandingApprovals<T>>::insert((id, owner, spender), StandingApproval {
+            replenish_to,
+            period,
+            last_replenish: <frame_system::Module<T>>::block_number(),
+        });
+
+        Self::deposit_event(RawEvent::StandingApprovalSet(
+            *id,
+            owner.clone(),
+            spender.clone(),
+            replenish_to,
+            period,
+        ));
+
+        Ok(())
+    }
+
+    pub fn inner_approve(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> DispatchResult {
+        ensure!(amount.is_zero() || Self::allow_delegation(id), Error::<T>::DelegationDisabled);
+        Self::ensure_allowance_within_cap(id, amount)?;
+
+        Self::set_allowance(id, owner, spender, amount);
+        <StandingApprovals<T>>::remove((id, owner, spender));
+
+        Self::deposit_event(RawEvent::Approval(
+            *id,
+            owner.clone(),
+            spender.clone(),
+            amount,
+        ));
+
+        Ok(())
+    }
+
+    fn ensure_allowance_within_cap(id: &T::AssetId, amount: T::TokenBalance) -> Result<(), Error<T>> {
+        if let Some(max) = Self::max_allowance(id) {
+            ensure!(amount <= max, Error::<T>::AllowanceTooLarge);
+        }
+
+        Ok(())
+    }
+
+    pub fn inner_batch_permit(
+        id: &T::AssetId,
+        permits: &[(T::AccountId, T::AccountId, T::TokenBalance, u32, T::Signature)],
+    ) -> DispatchResult {
+        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+
+        // Nonces already used by earlier permits in the batch, so an owner may sign several.
+        let mut next_nonces: Vec<(&T::AccountId, u32)> = Vec::new();
+        for (owner, spender, amount, nonce, signature) in permits.iter() {
+            let expected = next_nonces
+                .iter()
+                .rev()
+                .find(|(who, _)| *who == owner)
+                .map(|(_, nonce)| *nonce)
+                .unwrap_or_else(|| Self::permit_nonce((id, owner)));
+            ensure!(*nonce == expected, Error::<T>::BadNonce);
+
+            let message = (PERMIT_CONTEXT, id, spender, amount, nonce).encode();
+            ensure!(signature.verify(&message[..], owner), Error::<T>::BadSignature);
+            ensure!(amount.is_zero() || Self::allow_delegation(id), Error::<T>::DelegationDisabled);
+            Self::ensure_allowance_within_cap(id, *amount)?;
+
+            next_nonces.push((owner, nonce.saturating_add(1)));
+        }
+
+        for (owner, spender, amount, nonce, _) in permits.iter() {
+            Self::inner_approve(id, owner, spender, *amount)?;
+            <PermitNonce<T>>::insert((id, owner), nonce.saturating_add(1));
+        }
+
+        Ok(())
+    }
+
+    pub fn inner_reassign_allowance(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        old_spender: &T::AccountId,
+        new_spender: &T::AccountId,
+    ) -> DispatchResult {
+        let amount = <Allowances<T>>::get((id, owner, old_spender));
+        // Checked up front, since the old allowance is revoked before the new one is granted.
+        ensure!(amount.is_zero() || Self::allow_delegation(id), Error::<T>::DelegationDisabled);
+        Self::ensure_allowance_within_cap(id, amount)?;
+
+        Self::inner_approve(id, owner, old_spender, Zero::zero())?;
+        Self::inner_approve(id, owner, new_spender, amount)?;
+
+        Ok(())
+    }
+
+    pub fn allowances(id: &T::AssetId, owner: &T::AccountId, spender: &T::AccountId) -> T::TokenBalance {
+        <Allowances<T>>::get((id, owner, spender))
+    }
+
+    pub fn inner_mint(id: &T::AssetId, owner: &T::AccountId, amount: T::TokenBalance) -> DispatchResult {
+        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+        ensure!(!Self::is_mint_frozen(id), Error::<T>::MintingFrozen);
+        ensure!(!Self::is_wound_down(id), Error::<T>::AssetWoundDown);
+        Self::ensure_moves_raw_balance(id, amount)?;
+        if let Some(max) = Self::max_supply(id) {
+            ensure!(
+                Self::total_supply(id).saturating_add(amount) <= max,
+                Error::<T>::SupplyCapExceeded
+            );
+        }
+
+        let new_balance = Self::balance_of(id, owner).saturating_add(amount);
+
+        Self::set_balance(id, owner, new_balance);
+        Self::note_acquired(id, owner);
+        <TotalSupply<T>>::mutate(id, |supply| {
+            *supply = supply.saturating_add(amount);
+        });
+
+        Self::deposit_event(RawEvent::Minted(*id, owner.clone(), amount));
+
+        Ok(())
+    }
+
+    fn ensure_minter(id: &T::AssetId, who: &T::AccountId) -> DispatchResult {
+        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+        ensure!(
+            Self::owner(id).as_ref() == Some(who) || Self::is_minter((id, who)),
+            Error::<T>::NotMinter
+        );
+
+        Ok(())
+    }
+
+    pub fn inner_mint_vested(
+        id: &T::AssetId,
+        target: &T::AccountId,
+        schedule: VestingSchedule<T::TokenBalance, T::BlockNumber>,
+    ) -> DispatchResult {
+        ensure!(Self::vesting_locked(id, target).is_zero(), Error::<T>::VestingExists);
+        ensure!(
+            !schedule.locked.is_zero() && !schedule.per_block.is_zero(),
+            Error::<T>::AmountZero
+        );
+
+        Self::inner_mint(id, target, schedule.locked)?;
+
+        Self::deposit_event(RawEvent::VestingCreated(
+            *id,
+            target.clone(),
+            schedule.locked,
+            schedule.starting_block,
+        ));
+        <Vesting<T>>::insert((id, target), schedule);
+
+        Ok(())
+    }
+
+    /// Part of `who`'s balance still locked by its vesting schedule.
+    pub fn vesting_locked(id: &T::AssetId, who: &T::AccountId) -> T::TokenBalance {
+        let schedule = match Self::vesting((id, who)) {
+            Some(schedule) => schedule,
+            None => return Zero::zero(),
+        };
+
+        let now = <frame_system::Module<T>>::block_number();
+        let elapsed: u128 = now.saturating_sub(schedule.starting_block).saturated_into();
+        let vested = schedule.per_block.saturated_into::<u128>().saturating_mul(elapsed);
+
+        schedule.locked.saturating_sub(vested.saturated_into())
+    }
+
+    /// Part of `who`'s balance that can't be transferred or burned.
+    pub fn locked_balance(id: &T::AssetId, who: &T::AccountId) -> T::TokenBalance {
+        Self::vesting_locked(id, who)
+    }
+
+    pub fn inner_freeze_minting(id: &T::AssetId) {
+        <MintFrozen<T>>::insert(id, true);
+
+        Self::deposit_event(RawEvent::MintingFrozen(*id));
+    }
+
+    pub fn inner_reveal_transfer(
+        id: &T::AssetId,
+        from: &T::AccountId,
+        target: &T::AccountId,
+        amount: T::TokenBalance,
+        salt: [u8; 32],
+    ) -> DispatchResult {
+        let hash = T::Hashing::hash_of(&(id, target, amount, salt));
+        let committed = Self::transfer_commitment((from, hash)).ok_or(Error::<T>::BadReveal)?;
+        ensure!(
+            <frame_system::Module<T>>::block_number() >= committed.saturating_add(T::RevealDelay::get()),
+            Error::<T>::TooEarly
+        );
+
+        Self::inner_transfer(id, from, target, amount)?;
+        <TransferCommitments<T>>::remove((from, hash));
+
+        Ok(())
+    }
+
+    pub fn inner_rebase(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        numerator: u32,
+        denominator: u32,
+    ) -> DispatchResult {
+        ensure!(numerator > 0 && denominator > 0, Error::<T>::InvalidRebase);
+        ensure!(Self::distribution(id).is_none(), Error::<T>::DistributionInProgress);
+
+        let factor = Self::scaling_factor(id).unwrap_or(SCALING_ACCURACY);
+        let factor = multiply_by_rational(factor, numerator.into(), denominator.into())
+            .map_err(|_| Error::<T>::InvalidRebase)?;
+        ensure!(!factor.is_zero(), Error::<T>::InvalidRebase);
+        // The supply is the scaled raw supply plus the tokens held outside balances, which are
+        // not rebased.
+        let raw_supply = Self::raw_supply(id);
+        let held = Self::total_supply(id).saturating_sub(Self::scaled(id, raw_supply));
+        let target: T::TokenBalance = multiply_by_rational(
+            Self::scaled(id, raw_supply).saturated_into(),
+            numerator.into(),
+            denominator.into(),
+        )
+        .map_err(|_| Error::<T>::InvalidRebase)?
+        .saturated_into();
+        if let Some(max) = Self::max_supply(id) {
+            ensure!(target.saturating_add(held) <= max, Error::<T>::SupplyCapExceeded);
+        }
+
+        <ScalingFactor<T>>::insert(id, factor);
+
+        // Credit the owner with the raw dust: the largest raw supply whose scaled value stays
+        // within the target, less the raw supply already stored.
+        let mut new_raw_supply = Self::unscaled(id, target.saturating_add(One::one()));
+        if Self::scaled(id, new_raw_supply) > target {
+            new_raw_supply = new_raw_supply.saturating_sub(One::one());
+        }
+        let dust = new_raw_supply.max(raw_supply) - raw_supply;
+        let old_raw = <Balances<T>>::get((id, owner));
+        Self::set_raw_balance(id, owner, old_raw.saturating_add(dust));
+
+        <TotalSupply<T>>::insert(id, Self::scaled(id, Self::raw_supply(id)).saturating_add(held));
+
+        Self::deposit_event(RawEvent::Rebased(*id, numerator, denominator));
+
+        Ok(())
+    }
 
-        <Balances<T>>::mutate((id, owner), |balance| *balance = new_balance);
+    pub fn inner_burn_from_batch(
+        id: &T::AssetId,
+        spender: &T::AccountId,
+        burns: Vec<(T::AccountId, T::TokenBalance)>,
+    ) -> DispatchResult {
+        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+
+        // Entries burning from the same account are checked against its allowance together.
+        let mut totals: Vec<(T::AccountId, T::TokenBalance)> = Vec::new();
+        for (from, amount) in burns {
+            ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+            match totals.iter_mut().find(|(who, _)| *who == from) {
+                Some((_, total)) => *total = total.saturating_add(amount),
+                None => totals.push((from, amount)),
+            }
+        }
+
+        let mut updates = Vec::with_capacity(totals.len());
+        for (from, total) in totals.iter() {
+            ensure!(from == spender || Self::allow_delegation(id), Error::<T>::DelegationDisabled);
+            let (allowance, replenished) = Self::replenished_allowance(id, from, spender);
+            let new_allowance = allowance
+                .checked_sub(total)
+                .ok_or(Error::<T>::AllowanceLow)?;
+            Self::ensure_can_burn(id, from, *total)?;
+
+            updates.push((new_allowance, replenished));
+        }
+
+        for ((from, total), (new_allowance, replenished)) in totals.iter().zip(updates) {
+            Self::inner_burn(id, from, *total)?;
+            Self::set_allowance(id, from, spender, new_allowance);
+            if let Some(approval) = replenished {
+                <StandingApprovals<T>>::insert((id, from, spender), approval);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn inner_burn(id: &T::AssetId, owner: &T::AccountId, amount: T::TokenBalance) -> DispatchResult {
+        let new_balance = Self::ensure_can_burn(id, owner, amount)?;
+
+        Self::set_balance(id, owner, new_balance);
         <TotalSupply<T>>::mutate(id, |supply| {
             *supply = supply.saturating_sub(amount);
         });
@@ -246,4 +3436,198 @@ impl<T: Trait> Module<T> {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Check that `who` may burn `amount`, returning its balance after the burn.
+    fn ensure_can_burn(
+        id: &T::AssetId,
+        who: &T::AccountId,
+        amount: T::TokenBalance,
+    ) -> Result<T::TokenBalance, Error<T>> {
+        ensure!(Self::asset_info(id).is_some(), Error::<T>::AssetNotExists);
+        Self::ensure_moves_raw_balance(id, amount)?;
+
+        let new_balance = Self::balance_of(id, who)
+            .checked_sub(&amount)
+            .ok_or(Error::<T>::BalanceLow)?;
+        ensure!(new_balance >= Self::locked_balance(id, who), Error::<T>::BalanceLocked);
+
+        Ok(new_balance)
+    }
+
+    pub fn inner_redeem(id: &T::AssetId, who: &T::AccountId, amount: T::TokenBalance) -> DispatchResult {
+        ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+        let (rate, reserve) = Self::redemption(id).ok_or(Error::<T>::RedemptionDisabled)?;
+        ensure!(!Self::is_asset_frozen(id), Error::<T>::AssetFrozen);
+        ensure!(!Self::is_frozen((id, who)), Error::<T>::AccountFrozen);
+        Self::ensure_can_burn(id, who, amount)?;
+
+        let payout = Self::to_native(id, amount)
+            .and_then(|amount| amount.checked_mul(&rate))
+            .ok_or(Error::<T>::ConversionFailed)?;
+        ensure!(T::Currency::free_balance(&reserve) >= payout, Error::<T>::ReserveInsufficient);
+
+        T::Currency::transfer(&reserve, who, payout, ExistenceRequirement::AllowDeath)?;
+        Self::inner_burn(id, who, amount)?;
+
+        Self::deposit_event(RawEvent::Redeemed(*id, who.clone(), amount, payout));
+
+        Ok(())
+    }
+
+    /// Advance a distribution of `id` by up to `max` steps, starting it if none is in progress.
+    /// Each step either snapshots one balance entry or pays one snapshotted holder. Shares follow
+    /// holders' balances when the distribution started, relative to the supply at that time, so
+    /// transfers of `id` while it runs do not change them.
+    pub fn inner_distribute(
+        id: &T::AssetId,
+        owner: &T::AccountId,
+        reward_id: &T::AssetId,
+        total_reward: T::TokenBalance,
+        max: u32,
+    ) -> DispatchResult {
+        let mut distribution = match Self::distribution(id) {
+            Some(distribution) => {
+                ensure!(
+                    distribution.reward_id == *reward_id && distribution.total_reward == total_reward,
+                    Error::<T>::DistributionInProgress
+                );
+                distribution
+            }
+            None => {
+                ensure!(!total_reward.is_zero(), Error::<T>::AmountZero);
+                let new_balance = Self::ensure_can_burn(reward_id, owner, total_reward)?;
+                Self::set_balance(reward_id, owner, new_balance);
+
+                Distribution {
+                    reward_id: *reward_id,
+                    total_reward,
+                    supply: Self::total_supply(id),
+                    paid: Zero::zero(),
+                    snapshot_cursor: Some(<Balances<T>>::final_prefix().to_vec()),
+                }
+            }
+        };
+
+        let mut steps = 0;
+        if let Some(cursor) = distribution.snapshot_cursor.take() {
+            let (visited, cursor) = Self::snapshot_balances(id, cursor, max);
+            steps = visited;
+            distribution.snapshot_cursor = cursor;
+        }
+
+        if distribution.snapshot_cursor.is_none() {
+            let holders: Vec<(T::AccountId, T::TokenBalance)> = <DistributionSnapshots<T>>::iter_prefix(id)
+                .take(max.saturating_sub(steps) as usize)
+                .collect();
+            let supply: u128 = distribution.supply.saturated_into();
+            for (who, balance) in holders {
+                <DistributionSnapshots<T>>::remove(id, &who);
+                let share: T::TokenBalance =
+                    multiply_by_rational(balance.saturated_into(), total_reward.saturated_into(), supply)
+                        .unwrap_or(0)
+                        .saturated_into();
+                let share = share.min(total_reward.saturating_sub(distribution.paid));
+
+                Self::set_balance(reward_id, &who, Self::balance_of(reward_id, &who).saturating_add(share));
+                distribution.paid = distribution.paid.saturating_add(share);
+            }
+        }
+
+        let paid_all = <DistributionSnapshots<T>>::iter_prefix(id).next().is_none();
+        if distribution.snapshot_cursor.is_none() && paid_all {
+            let remainder = total_reward.saturating_sub(distribution.paid);
+            Self::set_balance(reward_id, owner, Self::balance_of(reward_id, owner).saturating_add(remainder));
+            <Distributions<T>>::remove(id);
+
+            Self::deposit_event(RawEvent::Distributed(*id, *reward_id, total_reward));
+        } else {
+            <Distributions<T>>::insert(id, distribution);
+        }
+
+        Ok(())
+    }
+
+    /// Snapshot the balances of `id` among the next `max` balance entries of any asset after the
+    /// raw key `cursor`, returning the entries visited and the cursor to resume from, `None` once
+    /// every entry has been visited. Holders whose balance changed since the distribution
+    /// started already have their earlier balance recorded and are skipped.
+    fn snapshot_balances(id: &T::AssetId, mut cursor: Vec<u8>, max: u32) -> (u32, Option<Vec<u8>>) {
+        let prefix = <Balances<T>>::final_prefix();
+        let mut visited = 0;
+        while visited < max {
+            let key = match sp_io::storage::next_key(&cursor) {
+                Some(key) if key.starts_with(&prefix) => key,
+                _ => return (visited, None),
+            };
+
+            // Keys are the prefix, a 16 byte hash and the encoded `(asset, account)`.
+            let entry = key.get(prefix.len() + 16..).and_then(|mut raw| {
+                <(T::AssetId, T::AccountId)>::decode(&mut raw).ok()
+            });
+            if let Some((asset, who)) = entry {
+                if asset == *id && !<DistributionSnapshots<T>>::contains_key(id, &who) {
+                    let balance = Self::scaled(id, <Balances<T>>::get((id, &who)));
+                    <DistributionSnapshots<T>>::insert(id, &who, balance);
+                }
+            }
+            visited += 1;
+            cursor = key;
+        }
+
+        (visited, Some(cursor))
+    }
+
+    /// Record `who`'s balance of `id` before it changes while a distribution of `id` is still
+    /// being snapshotted, so the snapshot keeps the balance the distribution started with.
+    fn note_distribution_balance(id: &T::AssetId, who: &T::AccountId, raw_balance: T::TokenBalance) {
+        let snapshotting = Self::distribution(id).map_or(false, |d| d.snapshot_cursor.is_some());
+        if snapshotting && !<DistributionSnapshots<T>>::contains_key(id, who) {
+            <DistributionSnapshots<T>>::insert(id, who, Self::scaled(id, raw_balance));
+        }
+    }
+
+    /// Remove the per-asset state of `id` and run the `OnAssetDestroyed` hook. Entries keyed by
+    /// asset and account are left behind; they hold no balance and the id is never reused.
+    fn finish_destroy(id: &T::AssetId) {
+        <TotalSupply<T>>::remove(id);
+        <InitialSupply<T>>::remove(id);
+        <AssetInfos<T>>::remove(id);
+        <Owners<T>>::remove(id);
+        <AggregateTransferEvents<T>>::remove(id);
+        <TransferEventVerbosity<T>>::remove(id);
+        <TransferSummaries<T>>::remove(id);
+        <HolderCount<T>>::remove(id);
+        <AllowanceCount<T>>::remove(id);
+        <MinHoldBlocks<T>>::remove(id);
+        <TradingStartBlock<T>>::remove(id);
+        <TransferFees<T>>::remove(id);
+        <FeeCollectors<T>>::remove(id);
+        <AssetFrozen<T>>::remove(id);
+        <FreezeRenounced<T>>::remove(id);
+        <AllowDelegation<T>>::remove(id);
+        <RestrictDestinations<T>>::remove(id);
+        <MaxSupply<T>>::remove(id);
+        <MaxAllowance<T>>::remove(id);
+        <MintFrozen<T>>::remove(id);
+        <WoundDown<T>>::remove(id);
+        <MinBalance<T>>::remove(id);
+        <MinPrecision<T>>::remove(id);
+        <DisplayDecimals<T>>::remove(id);
+        <Redemptions<T>>::remove(id);
+        <ExchangeRates<T>>::remove(id);
+        <LargeTransferConfirmation<T>>::remove(id);
+        <RecentTransfers<T>>::remove(id);
+        <BlockVolumes<T>>::remove(id);
+        <BurnedToAddress<T>>::remove(id);
+        <Enumerable<T>>::remove(id);
+        <CountRecomputations<T>>::remove(id);
+        <ScalingFactor<T>>::remove(id);
+        if Self::fee_asset().map(|(fee_id, _)| fee_id) == Some(*id) {
+            FeeAsset::<T>::kill();
+        }
+
+        T::OnAssetDestroyed::on_destroyed(id);
+
+        Self::deposit_event(RawEvent::Destroyed(*id));
+    }
+}
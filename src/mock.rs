@@ -1,8 +1,9 @@
-use crate::{Module, Trait};
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use crate::{Module, OnAssetDestroyed, Trait, WeightInfo};
+use std::cell::RefCell;
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
     Perbill,
 };
@@ -11,6 +12,18 @@ impl_outer_origin! {
     pub enum Origin for Test where system = frame_system {}
 }
 
+mod erc20 {
+    pub use crate::Event;
+}
+
+impl_outer_event! {
+    pub enum TestEvent for Test {
+        frame_system<T>,
+        pallet_balances<T>,
+        erc20<T>,
+    }
+}
+
 #[derive(Clone, Eq, PartialEq)]
 pub struct Test;
 
@@ -19,6 +32,11 @@ parameter_types! {
     pub const MaximumBlockWeight: Weight = 1024;
     pub const MaximumBlockLength: u32 = 2 * 1024;
     pub const AvailableBlockRatio: Perbill = Perbill::one();
+    pub const BurnAddress: u64 = 0;
+    pub const MaxBatchSize: u32 = 4;
+    pub const TrackCounterparties: bool = true;
+    pub const ExistentialDeposit: u64 = 1;
+    pub const RevealDelay: u64 = 2;
 }
 
 impl frame_system::Trait for Test {
@@ -26,7 +44,7 @@ impl frame_system::Trait for Test {
     type PalletInfo = ();
     type OnNewAccount = ();
     type SystemWeightInfo = ();
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<u64>;
     type OnKilledAccount = ();
     type MaximumBlockWeight = MaximumBlockWeight;
     type BlockExecutionWeight = ();
@@ -43,19 +61,62 @@ impl frame_system::Trait for Test {
     type Index = u64;
     type Lookup = IdentityLookup<Self::AccountId>;
     type Header = Header;
-    type Event = ();
+    type Event = TestEvent;
     type BlockHashCount = BlockHashCount;
     type AvailableBlockRatio = AvailableBlockRatio;
     type MaximumBlockLength = MaximumBlockLength;
 
 }
 
+impl pallet_balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = u64;
+    type Event = TestEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
 impl Trait for Test {
-    type Event = ();
+    type Event = TestEvent;
     type TokenBalance = u64;
     type AssetId = u32;
+    type BurnAddress = BurnAddress;
+    type MaxBatchSize = MaxBatchSize;
+    type Signature = TestSignature;
+    type Signer = UintAuthorityId;
+    type TrackCounterparties = TrackCounterparties;
+    type Currency = NativeBalances;
+    type OnAssetDestroyed = RecordDestroyed;
+    type BalanceConverter = ();
+    type RevealDelay = RevealDelay;
+    type WeightInfo = TestWeightInfo;
+}
+
+thread_local! {
+    pub static DESTROYED: RefCell<Vec<u32>> = RefCell::new(Vec::new());
+}
+
+/// Records destroyed asset ids in `DESTROYED`.
+pub struct RecordDestroyed;
+
+impl OnAssetDestroyed<u32> for RecordDestroyed {
+    fn on_destroyed(id: &u32) {
+        DESTROYED.with(|destroyed| destroyed.borrow_mut().push(*id));
+    }
+}
+
+pub struct TestWeightInfo;
+
+impl WeightInfo for TestWeightInfo {
+    fn transfer() -> Weight {
+        1_000
+    }
 }
 
+pub type System = frame_system::Module<Test>;
+pub type NativeBalances = pallet_balances::Module<Test>;
 pub type Assets = Module<Test>;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -0,0 +1,126 @@
+//! Runtime API definition for reading token state from outside the runtime.
+//!
+//! Many of these queries read one asset's state by iterating a storage map shared by every asset,
+//! so the pallet functions behind them are only meant to be called through this API.
+
+use crate::{AssetConfig, PalletLimits};
+use codec::Codec;
+use frame_support::weights::Weight;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+    pub trait TokenApi<AccountId, AssetId, TokenBalance, BlockNumber, Hash> where
+        AccountId: Codec,
+        AssetId: Codec,
+        TokenBalance: Codec,
+        BlockNumber: Codec,
+        Hash: Codec,
+    {
+        /// The `n` assets with the largest total supply, largest first.
+        fn largest_assets(n: u32) -> Vec<(AssetId, TokenBalance)>;
+
+        /// Number of `(holders, allowances)` storage entries of an asset.
+        fn asset_storage_stats(id: AssetId) -> (u32, u32);
+
+        /// All settings of an asset, or `None` if it doesn't exist.
+        fn asset_config(id: AssetId) -> Option<AssetConfig<AccountId, TokenBalance, BlockNumber>>;
+
+        /// Total supply of each of `ids`, skipping assets that don't exist.
+        fn combined_supply(ids: Vec<AssetId>) -> Vec<(AssetId, TokenBalance)>;
+
+        /// Amount that can still be minted, `None` if unlimited or the asset doesn't exist.
+        fn remaining_mintable(id: AssetId) -> Option<TokenBalance>;
+
+        /// Balance of `who` in basis points of the asset's total supply.
+        fn supply_share_bps(id: AssetId, who: AccountId) -> u16;
+
+        /// Whether `from` has ever transferred the asset to `to`.
+        fn has_transacted(id: AssetId, from: AccountId, to: AccountId) -> bool;
+
+        /// Supply the asset was issued with.
+        fn initial_supply(id: AssetId) -> TokenBalance;
+
+        /// For each `(owner, amount)`, whether `spender`'s allowance and `owner`'s balance
+        /// both cover `amount`.
+        fn allowances_sufficient(
+            id: AssetId,
+            spender: AccountId,
+            requests: Vec<(AccountId, TokenBalance)>,
+        ) -> Vec<bool>;
+
+        /// Merkle root over the asset's `(account, balance)` pairs, for inclusion proofs.
+        fn balances_root(id: AssetId) -> Hash;
+
+        /// Most recent transfers of an asset as `(from, to, amount, block)`, oldest first.
+        fn recent_transfers(id: AssetId) -> Vec<(AccountId, AccountId, TokenBalance, BlockNumber)>;
+
+        /// Whether `id` was ever allocated, telling apart ids never issued from assets that
+        /// no longer exist.
+        fn asset_id_ever_used(id: AssetId) -> bool;
+
+        /// `(fee_bps, burn_bps, collector)` charged on transfers of an asset.
+        fn fee_config(id: AssetId) -> Option<(u16, u16, AccountId)>;
+
+        /// Balance of `a` minus balance of `b`.
+        fn balance_diff(id: AssetId, a: AccountId, b: AccountId) -> i128;
+
+        /// Limits the pallet enforces on calls.
+        fn pallet_limits() -> PalletLimits;
+
+        /// Share of the asset's supply held by its largest holder, in basis points.
+        fn concentration(id: AssetId) -> u16;
+
+        /// Whether `who` holds the asset's entire supply.
+        fn is_sole_holder(id: AssetId, who: AccountId) -> bool;
+
+        /// The `n` largest holders of an asset, empty if the asset isn't enumerable.
+        fn top_holders(id: AssetId, n: u32) -> Vec<(AccountId, TokenBalance)>;
+
+        /// One-based rank of `who` among the asset's holders, `None` if it holds none or the
+        /// asset isn't enumerable.
+        fn holder_rank(id: AssetId, who: AccountId) -> Option<u32>;
+
+        /// Stored raw balance of `who` and the balance it represents after rebases.
+        fn raw_and_scaled_balance(id: AssetId, who: AccountId) -> (TokenBalance, TokenBalance);
+
+        /// `(free, reserved, supply)` of an asset; `free + reserved` should equal `supply`.
+        fn asset_solvency(id: AssetId) -> (TokenBalance, TokenBalance, TokenBalance);
+
+        /// `(amount, deadline)` of pending two-step transfers to `who`, due once their senders
+        /// confirm them.
+        fn pending_transfers(id: AssetId, who: AccountId) -> Vec<(TokenBalance, BlockNumber)>;
+
+        /// Fingerprint of an asset's metadata, settings, supply and balances.
+        fn asset_state_digest(id: AssetId) -> Hash;
+
+        /// Volume of an asset transferred in the last `n` blocks.
+        fn volume_last_n_blocks(id: AssetId, n: u32) -> TokenBalance;
+
+        /// Accounts allowed to mint an asset, its owner first.
+        fn minters(id: AssetId) -> Vec<AccountId>;
+
+        /// Up to `limit` of the allowances `owner` has granted, from the `start_index`-th by spender.
+        /// Reads all of `owner`'s allowances of the asset, whatever the page.
+        fn granted_allowances(
+            id: AssetId,
+            owner: AccountId,
+            start_index: u32,
+            limit: u32,
+        ) -> Vec<(AccountId, TokenBalance)>;
+
+        /// Estimated weight of a batch of `call_count` transfers.
+        fn estimate_batch_weight(call_count: u32) -> Weight;
+
+        /// Supply of an asset less the tokens permanently locked.
+        fn circulating_supply(id: AssetId) -> TokenBalance;
+
+        /// `(circulating, reserved, vesting_locked, permanently_locked)` parts of an asset's supply.
+        fn supply_composition(id: AssetId) -> (TokenBalance, TokenBalance, TokenBalance, TokenBalance);
+
+        /// `(asset, owner, amount)` of every allowance granted to `spender`.
+        fn spendable_assets(spender: AccountId) -> Vec<(AssetId, AccountId, TokenBalance)>;
+
+        /// `(net_to_recipient, fee, burned)` of a transfer of `amount`.
+        fn quote_transfer(id: AssetId, amount: TokenBalance) -> (TokenBalance, TokenBalance, TokenBalance);
+    }
+}
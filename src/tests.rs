@@ -1,5 +1,19 @@
-use crate::{mock::*, AssetInfo, Error, Name, Symbol};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    mock::*, AllowanceCount, AssetConfig, AssetInfo, BalanceConverter, Call, Error, EventVerbosity,
+    FormattedBalance, HolderCount, MinBalance, Name, OwnerAllowances, PalletLimits, RawEvent,
+    RawSupply, SpenderAllowances, Symbol, VestingSchedule,
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, Get, OnFinalize, OnRuntimeUpgrade},
+    weights::GetDispatchInfo,
+    StorageDoubleMap,
+};
+use sp_runtime::{
+    testing::TestSignature,
+    traits::{BadOrigin, BlakeTwo256, Hash},
+};
 
 const TEST_ASSET_NAME: Name = *b"polkaswapnk_tom";
 const TEST_ASSET_SYMBOL: Symbol = *b"pstom";
@@ -192,4 +206,2639 @@ fn inner_mint_transfer_burn_should_work() {
             Error::<Test>::BalanceLow,
         );
     });
-}
\ No newline at end of file
+}
+#[test]
+fn largest_assets_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::issue(Origin::signed(1), 300, TEST_ASSET_INFO));
+        assert_ok!(Assets::issue(Origin::signed(2), 200, TEST_ASSET_INFO));
+        assert_ok!(Assets::issue(Origin::signed(2), 50, TEST_ASSET_INFO));
+
+        assert_eq!(Assets::largest_assets(2), vec![(1, 300), (2, 200)]);
+        assert_eq!(
+            Assets::largest_assets(10),
+            vec![(1, 300), (2, 200), (0, 100), (3, 50)]
+        );
+        assert_eq!(Assets::largest_assets(0), vec![]);
+    });
+}
+
+#[test]
+fn aggregate_transfer_events_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_aggregate_transfer_events(Origin::signed(1), 0, true));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 20));
+        assert!(!System::events()
+            .iter()
+            .any(|r| matches!(r.event, TestEvent::erc20(RawEvent::Transferred(..)))));
+
+        Assets::on_finalize(1);
+        assert_eq!(
+            System::events().last().map(|r| r.event.clone()),
+            Some(TestEvent::erc20(RawEvent::TransfersAggregated(0, 2, 30)))
+        );
+    });
+}
+
+#[test]
+fn aggregate_transfer_events_should_not_work_for_non_owner() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_noop!(
+            Assets::set_aggregate_transfer_events(Origin::signed(2), 0, true),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn reassign_allowance_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 20));
+
+        assert_ok!(Assets::reassign_allowance(Origin::signed(1), 0, 2, 3));
+        assert_eq!(Assets::allowances(&0, &1, &2), 0);
+        assert_eq!(Assets::allowances(&0, &1, &3), 20);
+
+        assert_noop!(
+            Assets::transfer_from(Origin::signed(2), 0, 1, 4, 10),
+            Error::<Test>::AllowanceLow
+        );
+        assert_ok!(Assets::transfer_from(Origin::signed(3), 0, 1, 4, 10));
+        assert_eq!(Assets::balance_of(&0, &4), 10);
+    });
+}
+
+#[test]
+fn reassign_allowance_should_not_revoke_when_new_allowance_fails() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 20));
+
+        assert_ok!(Assets::set_max_allowance(Origin::signed(1), 0, Some(10)));
+        assert_noop!(
+            Assets::reassign_allowance(Origin::signed(1), 0, 2, 3),
+            Error::<Test>::AllowanceTooLarge
+        );
+
+        assert_ok!(Assets::set_max_allowance(Origin::signed(1), 0, None));
+        assert_ok!(Assets::set_allow_delegation(Origin::signed(1), 0, false));
+        assert_noop!(
+            Assets::reassign_allowance(Origin::signed(1), 0, 2, 3),
+            Error::<Test>::DelegationDisabled
+        );
+        assert_eq!(Assets::allowances(&0, &1, &2), 20);
+    });
+}
+
+#[test]
+fn asset_storage_stats_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_eq!(Assets::asset_storage_stats(&0), (1, 0));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 50));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 10));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 3, 5));
+        assert_eq!(Assets::asset_storage_stats(&0), (2, 2));
+
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 3, 0));
+        assert_ok!(Assets::transfer_from(Origin::signed(2), 0, 1, 3, 10));
+        assert_eq!(Assets::asset_storage_stats(&0), (2, 0));
+        assert_eq!(Assets::asset_storage_stats(&1), (0, 0));
+    });
+}
+
+#[test]
+fn transfer_gate_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+
+        assert_ok!(Assets::set_transfer_gate(Origin::signed(1), 0, 2, false));
+        assert_noop!(
+            Assets::transfer(Origin::signed(2), 0, 3, 10),
+            Error::<Test>::TransfersDisabled
+        );
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+
+        assert_ok!(Assets::set_transfer_gate(Origin::signed(1), 0, 2, true));
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 10));
+        assert_eq!(Assets::balance_of(&0, &3), 10);
+    });
+}
+
+#[test]
+fn set_transfer_gate_should_not_work_for_non_owner() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_noop!(
+            Assets::set_transfer_gate(Origin::signed(2), 0, 1, false),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn min_hold_blocks_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_min_hold_blocks(Origin::signed(1), 0, 10));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+        assert_eq!(Assets::acquired_at((0, 2)), Some(1));
+
+        System::set_block_number(10);
+        assert_noop!(
+            Assets::transfer(Origin::signed(2), 0, 3, 10),
+            Error::<Test>::HoldPeriodActive
+        );
+
+        System::set_block_number(11);
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 10));
+        assert_eq!(Assets::balance_of(&0, &3), 10);
+    });
+}
+
+#[test]
+fn min_hold_blocks_should_restart_on_receive() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_min_hold_blocks(Origin::signed(1), 0, 10));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+
+        System::set_block_number(8);
+        assert_ok!(Assets::inner_mint(&0, &2, 10));
+
+        System::set_block_number(11);
+        assert_noop!(
+            Assets::transfer(Origin::signed(2), 0, 3, 10),
+            Error::<Test>::HoldPeriodActive
+        );
+
+        System::set_block_number(18);
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 10));
+    });
+}
+
+#[test]
+fn transfer_fee_should_go_to_owner_by_default() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+        assert_ok!(Assets::set_transfer_fee(Origin::signed(1), 0, 1_000));
+
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 30));
+        assert_eq!(Assets::balance_of(&0, &1), 53);
+        assert_eq!(Assets::balance_of(&0, &2), 20);
+        assert_eq!(Assets::balance_of(&0, &3), 27);
+        assert_eq!(Assets::total_supply(&0), 100);
+    });
+}
+
+#[test]
+fn set_fee_collector_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_transfer_fee(Origin::signed(1), 0, 1_000));
+        assert_ok!(Assets::set_fee_collector(Origin::signed(1), 0, 9));
+        assert_eq!(Assets::fee_collector(&0), Some(9));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+        assert_eq!(Assets::balance_of(&0, &1), 50);
+        assert_eq!(Assets::balance_of(&0, &2), 45);
+        assert_eq!(Assets::balance_of(&0, &9), 5);
+
+        assert_noop!(
+            Assets::set_fee_collector(Origin::signed(2), 0, 2),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn set_transfer_fee_should_not_work_above_max() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_noop!(
+            Assets::set_transfer_fee(Origin::signed(1), 0, 10_001),
+            Error::<Test>::InvalidFee
+        );
+    });
+}
+
+#[test]
+fn asset_config_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Assets::asset_config(&0), None);
+
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_transfer_fee(Origin::signed(1), 0, 250));
+        assert_ok!(Assets::set_fee_collector(Origin::signed(1), 0, 9));
+        assert_ok!(Assets::set_min_hold_blocks(Origin::signed(1), 0, 5));
+
+        assert_eq!(
+            Assets::asset_config(&0),
+            Some(AssetConfig {
+                owner: 1,
+                fee_bps: 250,
+                fee_collector: 9,
+                min_hold_blocks: 5,
+                aggregate_transfer_events: false,
+                allow_delegation: true,
+                restrict_destinations: false,
+                max_supply: None,
+                mint_frozen: false,
+                min_balance: 0,
+                min_precision: None,
+                asset_frozen: false,
+                freeze_renounced: false,
+                display_decimals: None,
+                wound_down: false,
+                event_verbosity: EventVerbosity::Full,
+                enumerable: true,
+                trading_start_block: 0,
+                max_allowance: None,
+                large_transfer_confirmation: None,
+                wash_trade_protection: false,
+            })
+        );
+    });
+}
+
+#[test]
+fn transfer_from_should_not_work_for_nonexistent_asset() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Assets::transfer_from(Origin::signed(2), 0, 1, 3, 10),
+            Error::<Test>::AssetNotExists
+        );
+        assert_noop!(
+            Assets::transfer(Origin::signed(1), 0, 2, 10),
+            Error::<Test>::AssetNotExists
+        );
+    });
+}
+
+#[test]
+fn transfer_to_burn_address_should_burn() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, BurnAddress::get(), 30));
+        assert_eq!(Assets::balance_of(&0, &1), 70);
+        assert_eq!(Assets::balance_of(&0, &BurnAddress::get()), 0);
+        assert_eq!(Assets::total_supply(&0), 70);
+    });
+}
+
+#[test]
+fn freeze_and_thaw_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+
+        assert_ok!(Assets::freeze(Origin::signed(1), 0, 2));
+        assert_noop!(
+            Assets::transfer(Origin::signed(2), 0, 3, 10),
+            Error::<Test>::AccountFrozen
+        );
+
+        assert_ok!(Assets::thaw(Origin::signed(1), 0, 2));
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 10));
+    });
+}
+
+#[test]
+fn freeze_many_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_ok!(Assets::freeze_many(Origin::signed(1), 0, vec![2, 3, 4]));
+        assert!(Assets::is_frozen((0, 2)));
+        assert!(Assets::is_frozen((0, 3)));
+        assert!(Assets::is_frozen((0, 4)));
+        assert!(!Assets::is_frozen((0, 1)));
+    });
+}
+
+#[test]
+fn freeze_many_should_not_work_above_max_batch_size() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_noop!(
+            Assets::freeze_many(Origin::signed(1), 0, vec![2, 3, 4, 5, 6]),
+            Error::<Test>::BatchTooLarge
+        );
+        assert_noop!(
+            Assets::freeze_many(Origin::signed(2), 0, vec![3]),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn simulate_transfer_from_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 20));
+        let events = System::events().len();
+
+        assert_eq!(Assets::simulate_transfer_from(&0, &1, &2, &3, 15).ok(), Some(5));
+        assert_eq!(Assets::allowances(&0, &1, &2), 20);
+        assert_eq!(Assets::balance_of(&0, &1), 100);
+        assert_eq!(System::events().len(), events);
+    });
+}
+
+#[test]
+fn simulate_transfer_from_should_report_errors() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 200));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 3, 10));
+
+        assert!(matches!(
+            Assets::simulate_transfer_from(&0, &1, &3, &4, 50),
+            Err(Error::<Test>::AllowanceLow)
+        ));
+        assert!(matches!(
+            Assets::simulate_transfer_from(&0, &1, &2, &4, 150),
+            Err(Error::<Test>::BalanceLow)
+        ));
+        assert!(matches!(
+            Assets::simulate_transfer_from(&1, &1, &2, &4, 10),
+            Err(Error::<Test>::AssetNotExists)
+        ));
+    });
+}
+
+#[test]
+fn disabled_delegation_should_reject_approvals() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 20));
+        assert_ok!(Assets::set_allow_delegation(Origin::signed(1), 0, false));
+
+        assert_noop!(
+            Assets::approve(Origin::signed(1), 0, 3, 20),
+            Error::<Test>::DelegationDisabled
+        );
+        assert_noop!(
+            Assets::transfer_from(Origin::signed(2), 0, 1, 3, 10),
+            Error::<Test>::DelegationDisabled
+        );
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 10));
+        assert_eq!(Assets::balance_of(&0, &3), 10);
+
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 0));
+        assert_eq!(Assets::allowances(&0, &1, &2), 0);
+    });
+}
+
+#[test]
+fn force_revoke_allowance_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 3, 20));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 4, 20));
+
+        assert_noop!(
+            Assets::force_revoke_allowance(Origin::signed(3), 0, 2, 3),
+            Error::<Test>::NotOwner
+        );
+
+        assert_ok!(Assets::force_revoke_allowance(Origin::signed(1), 0, 2, 3));
+        assert_eq!(Assets::allowances(&0, &2, &3), 0);
+        assert_noop!(
+            Assets::transfer_from(Origin::signed(3), 0, 2, 3, 10),
+            Error::<Test>::AllowanceLow
+        );
+
+        assert_ok!(Assets::force_revoke_allowance(Origin::root(), 0, 2, 4));
+        assert_eq!(Assets::allowances(&0, &2, &4), 0);
+    });
+}
+
+#[test]
+fn combined_supply_should_skip_missing_assets() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::issue(Origin::signed(1), 300, TEST_ASSET_INFO));
+
+        assert_eq!(
+            Assets::combined_supply(vec![1, 7, 0, 2]),
+            vec![(1, 300), (0, 100)]
+        );
+    });
+}
+
+#[test]
+fn transfer_signed_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let signature = TestSignature(1, (0u32, 3u64, 10u64, 0u32).encode());
+
+        assert_ok!(Assets::transfer_signed(Origin::signed(2), 0, 1, 3, 10, 0, signature));
+        assert_eq!(Assets::balance_of(&0, &1), 90);
+        assert_eq!(Assets::balance_of(&0, &3), 10);
+        assert_eq!(Assets::transfer_nonce((0, 1)), 1);
+    });
+}
+
+#[test]
+fn transfer_signed_should_not_work_with_replayed_nonce() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let signature = TestSignature(1, (0u32, 3u64, 10u64, 0u32).encode());
+        assert_ok!(Assets::transfer_signed(Origin::signed(2), 0, 1, 3, 10, 0, signature.clone()));
+
+        assert_noop!(
+            Assets::transfer_signed(Origin::signed(2), 0, 1, 3, 10, 0, signature),
+            Error::<Test>::BadNonce
+        );
+    });
+}
+
+#[test]
+fn transfer_signed_should_not_work_with_forged_signature() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        let forged = TestSignature(2, (0u32, 2u64, 10u64, 0u32).encode());
+        assert_noop!(
+            Assets::transfer_signed(Origin::signed(2), 0, 1, 2, 10, 0, forged),
+            Error::<Test>::BadSignature
+        );
+
+        let tampered = TestSignature(1, (0u32, 3u64, 10u64, 0u32).encode());
+        assert_noop!(
+            Assets::transfer_signed(Origin::signed(2), 0, 1, 2, 10, 0, tampered),
+            Error::<Test>::BadSignature
+        );
+    });
+}
+
+#[test]
+fn formatted_balance_should_insert_decimals() {
+    let formatted = |amount, decimals| format!("{}", FormattedBalance { amount, decimals });
+
+    assert_eq!(formatted(12345, 0), "12345");
+    assert_eq!(formatted(12345, 2), "123.45");
+    assert_eq!(formatted(5, 3), "0.005");
+    assert_eq!(formatted(0, 2), "0.00");
+    assert_eq!(formatted(1_500_000_000_000_000_000, 18), "1.500000000000000000");
+    assert_eq!(formatted(u128::max_value(), 0), "340282366920938463463374607431768211455");
+    assert_eq!(formatted(7, 40), "0.0000000000000000000000000000000000000007");
+}
+
+#[test]
+fn formatted_balance_should_use_asset_decimals() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_eq!(
+            format!("{:?}", Assets::formatted_balance(&0, 2_500_000_000_000_000_000)),
+            "2.500000000000000000"
+        );
+        assert_eq!(format!("{:?}", Assets::formatted_balance(&1, 25)), "25");
+    });
+}
+
+#[test]
+fn restricted_destinations_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_restrict_destinations(Origin::signed(1), 0, true));
+        assert_ok!(Assets::set_allowed_destination(Origin::signed(1), 0, 3, true));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 10));
+        assert_noop!(
+            Assets::transfer(Origin::signed(2), 0, 4, 10),
+            Error::<Test>::DestinationNotAllowed
+        );
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 1, 10));
+        assert_ok!(Assets::inner_mint(&0, &4, 10));
+
+        assert_ok!(Assets::set_allowed_destination(Origin::signed(1), 0, 3, false));
+        assert_noop!(
+            Assets::transfer(Origin::signed(2), 0, 3, 10),
+            Error::<Test>::DestinationNotAllowed
+        );
+    });
+}
+
+#[test]
+fn remaining_mintable_should_work_for_capped_asset() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_max_supply(Origin::signed(1), 0, Some(150)));
+        assert_eq!(Assets::remaining_mintable(&0), Some(50));
+
+        assert_ok!(Assets::inner_mint(&0, &2, 30));
+        assert_eq!(Assets::remaining_mintable(&0), Some(20));
+        assert_noop!(Assets::inner_mint(&0, &2, 21), Error::<Test>::SupplyCapExceeded);
+        assert_noop!(
+            Assets::set_max_supply(Origin::signed(1), 0, Some(100)),
+            Error::<Test>::SupplyCapExceeded
+        );
+    });
+}
+
+#[test]
+fn remaining_mintable_should_work_for_uncapped_asset() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_eq!(Assets::remaining_mintable(&0), None);
+
+        assert_ok!(Assets::set_max_supply(Origin::signed(1), 0, Some(150)));
+        assert_ok!(Assets::set_max_supply(Origin::signed(1), 0, None));
+        assert_eq!(Assets::remaining_mintable(&0), None);
+        assert_eq!(Assets::remaining_mintable(&1), None);
+    });
+}
+
+#[test]
+fn remaining_mintable_should_be_zero_when_mint_frozen() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_max_supply(Origin::signed(1), 0, Some(150)));
+        assert_ok!(Assets::freeze_minting(Origin::signed(1), 0));
+
+        assert_eq!(Assets::remaining_mintable(&0), Some(0));
+        assert_noop!(Assets::inner_mint(&0, &1, 1), Error::<Test>::MintingFrozen);
+    });
+}
+
+#[test]
+fn standing_approval_should_replenish_each_period() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_standing_approval(Origin::signed(1), 0, 2, 30, 10));
+        assert_eq!(Assets::allowances(&0, &1, &2), 30);
+
+        System::set_block_number(2);
+        assert_ok!(Assets::transfer_from(Origin::signed(2), 0, 1, 2, 20));
+        assert_eq!(Assets::allowances(&0, &1, &2), 10);
+
+        System::set_block_number(10);
+        assert_noop!(
+            Assets::transfer_from(Origin::signed(2), 0, 1, 2, 15),
+            Error::<Test>::AllowanceLow
+        );
+
+        System::set_block_number(11);
+        assert_ok!(Assets::transfer_from(Origin::signed(2), 0, 1, 2, 25));
+        assert_eq!(Assets::allowances(&0, &1, &2), 5);
+        assert_eq!(Assets::balance_of(&0, &2), 45);
+        assert_eq!(
+            Assets::standing_approval((0, 1, 2)).map(|approval| approval.last_replenish),
+            Some(11)
+        );
+    });
+}
+
+#[test]
+fn approve_should_cancel_standing_approval() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_standing_approval(Origin::signed(1), 0, 2, 30, 10));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 0));
+
+        System::set_block_number(20);
+        assert_eq!(Assets::standing_approval((0, 1, 2)), None);
+        assert_noop!(
+            Assets::transfer_from(Origin::signed(2), 0, 1, 2, 1),
+            Error::<Test>::AllowanceLow
+        );
+        assert_noop!(
+            Assets::set_standing_approval(Origin::signed(1), 0, 2, 30, 0),
+            Error::<Test>::PeriodZero
+        );
+    });
+}
+
+#[test]
+fn holder_count_should_stay_consistent_across_zero_crossings() {
+    new_test_ext().execute_with(|| {
+        let holders = || (1..=4).filter(|who| Assets::balance_of(&0, who) > 0).count() as u32;
+
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        for _ in 0..3 {
+            assert_ok!(Assets::inner_mint(&0, &2, 10));
+            assert_eq!(Assets::holder_count(&0), holders());
+            assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 10));
+            assert_eq!(Assets::holder_count(&0), holders());
+            assert_ok!(Assets::transfer(Origin::signed(3), 0, 2, 10));
+            assert_eq!(Assets::holder_count(&0), holders());
+            assert_ok!(Assets::inner_burn(&0, &2, 10));
+            assert_eq!(Assets::holder_count(&0), holders());
+            assert_ok!(Assets::transfer(Origin::signed(1), 0, 4, 100));
+            assert_eq!(Assets::holder_count(&0), holders());
+            assert_ok!(Assets::transfer(Origin::signed(4), 0, 1, 100));
+            assert_eq!(Assets::holder_count(&0), holders());
+        }
+        assert_eq!(Assets::holder_count(&0), 1);
+    });
+}
+
+#[test]
+fn supply_share_bps_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 300, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 100));
+
+        assert_eq!(Assets::supply_share_bps(&0, &1), 6_666);
+        assert_eq!(Assets::supply_share_bps(&0, &2), 3_333);
+        assert_eq!(Assets::supply_share_bps(&0, &3), 0);
+    });
+}
+
+#[test]
+fn supply_share_bps_should_not_overflow_for_large_balances() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), u64::max_value(), TEST_ASSET_INFO));
+
+        assert_eq!(Assets::supply_share_bps(&0, &1), 10_000);
+    });
+}
+
+#[test]
+fn supply_share_bps_should_be_zero_without_supply() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Assets::supply_share_bps(&0, &1), 0);
+
+        assert_ok!(Assets::issue(Origin::signed(1), 0, TEST_ASSET_INFO));
+        assert_eq!(Assets::supply_share_bps(&0, &1), 0);
+    });
+}
+
+#[test]
+fn transfer_no_dust_should_not_create_dust_holder() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        MinBalance::<Test>::insert(0, 10);
+
+        assert_noop!(
+            Assets::transfer_no_dust(Origin::signed(1), 0, 2, 9),
+            Error::<Test>::BalanceTooLow
+        );
+        assert_ok!(Assets::transfer_no_dust(Origin::signed(1), 0, 2, 10));
+        assert_ok!(Assets::transfer_no_dust(Origin::signed(1), 0, 2, 1));
+        assert_eq!(Assets::balance_of(&0, &2), 11);
+    });
+}
+
+#[test]
+fn transfer_no_dust_should_account_for_fees() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_transfer_fee(Origin::signed(1), 0, 1_000));
+        MinBalance::<Test>::insert(0, 10);
+
+        assert_noop!(
+            Assets::transfer_no_dust(Origin::signed(1), 0, 2, 10),
+            Error::<Test>::BalanceTooLow
+        );
+        assert_ok!(Assets::transfer_no_dust(Origin::signed(1), 0, 2, 12));
+        assert_eq!(Assets::balance_of(&0, &2), 11);
+    });
+}
+
+#[test]
+fn transfer_no_dust_should_work_without_min_balance() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_ok!(Assets::transfer_no_dust(Origin::signed(1), 0, 2, 1));
+        assert_eq!(Assets::balance_of(&0, &2), 1);
+    });
+}
+
+#[test]
+fn raising_min_balance_should_warn() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 5));
+
+        assert_ok!(Assets::set_min_balance(Origin::signed(1), 0, 10));
+        assert_eq!(Assets::min_balance(&0), 10);
+        assert_eq!(Assets::balance_of(&0, &2), 5);
+        assert!(System::events().iter().any(|r| r.event
+            == TestEvent::erc20(RawEvent::MinBalanceRaised(0, 0, 10))));
+        assert_noop!(
+            Assets::transfer_no_dust(Origin::signed(1), 0, 3, 9),
+            Error::<Test>::BalanceTooLow
+        );
+
+        assert_noop!(
+            Assets::set_min_balance(Origin::signed(2), 0, 20),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn lowering_min_balance_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_min_balance(Origin::signed(1), 0, 10));
+        System::reset_events();
+
+        assert_ok!(Assets::set_min_balance(Origin::signed(1), 0, 2));
+        assert_eq!(Assets::min_balance(&0), 2);
+        assert_eq!(
+            System::events().into_iter().map(|r| r.event).collect::<Vec<_>>(),
+            vec![TestEvent::erc20(RawEvent::MinBalanceSet(0, 2))]
+        );
+        assert_ok!(Assets::transfer_no_dust(Origin::signed(1), 0, 3, 2));
+    });
+}
+
+#[test]
+fn has_transacted_should_record_transfers() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert!(!Assets::has_transacted(&0, &1, &2));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        assert!(Assets::has_transacted(&0, &1, &2));
+        assert!(!Assets::has_transacted(&0, &2, &1));
+        assert!(!Assets::has_transacted(&1, &1, &2));
+    });
+}
+
+#[test]
+fn mint_vested_should_lock_minted_tokens() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let schedule = VestingSchedule { locked: 100, per_block: 10, starting_block: 10 };
+
+        assert_ok!(Assets::mint_vested(Origin::signed(1), 0, 2, schedule));
+        assert_eq!(Assets::balance_of(&0, &2), 100);
+        assert_eq!(Assets::total_supply(&0), 200);
+        assert!(System::events().iter().any(|r| r.event
+            == TestEvent::erc20(RawEvent::VestingCreated(0, 2, 100, 10))));
+
+        System::set_block_number(5);
+        assert_eq!(Assets::vesting_locked(&0, &2), 100);
+        assert_noop!(
+            Assets::transfer(Origin::signed(2), 0, 3, 1),
+            Error::<Test>::BalanceLocked
+        );
+
+        System::set_block_number(15);
+        assert_eq!(Assets::vesting_locked(&0, &2), 50);
+        assert_noop!(
+            Assets::transfer(Origin::signed(2), 0, 3, 51),
+            Error::<Test>::BalanceLocked
+        );
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 50));
+        assert_noop!(Assets::inner_burn(&0, &2, 1), Error::<Test>::BalanceLocked);
+
+        System::set_block_number(20);
+        assert_eq!(Assets::vesting_locked(&0, &2), 0);
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 50));
+    });
+}
+
+#[test]
+fn mint_vested_should_replace_fully_vested_schedule() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let schedule = VestingSchedule { locked: 100, per_block: 10, starting_block: 10 };
+        assert_ok!(Assets::mint_vested(Origin::signed(1), 0, 2, schedule.clone()));
+
+        System::set_block_number(19);
+        assert_noop!(
+            Assets::mint_vested(Origin::signed(1), 0, 2, schedule),
+            Error::<Test>::VestingExists
+        );
+
+        System::set_block_number(20);
+        let schedule = VestingSchedule { locked: 50, per_block: 5, starting_block: 20 };
+        assert_ok!(Assets::mint_vested(Origin::signed(1), 0, 2, schedule.clone()));
+        assert_eq!(Assets::vesting((0, 2)), Some(schedule));
+        assert_eq!(Assets::balance_of(&0, &2), 150);
+        assert_eq!(Assets::locked_balance(&0, &2), 50);
+    });
+}
+
+#[test]
+fn mint_vested_should_require_minter() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let schedule = VestingSchedule { locked: 100, per_block: 10, starting_block: 10 };
+
+        assert_noop!(
+            Assets::mint_vested(Origin::signed(3), 0, 2, schedule.clone()),
+            Error::<Test>::NotMinter
+        );
+
+        assert_ok!(Assets::add_minter(Origin::signed(1), 0, 3));
+        assert_ok!(Assets::mint_vested(Origin::signed(3), 0, 2, schedule.clone()));
+        assert_noop!(
+            Assets::mint_vested(Origin::signed(3), 0, 2, schedule.clone()),
+            Error::<Test>::VestingExists
+        );
+
+        assert_ok!(Assets::remove_minter(Origin::signed(1), 0, 3));
+        assert_noop!(
+            Assets::mint_vested(Origin::signed(3), 0, 4, schedule),
+            Error::<Test>::NotMinter
+        );
+    });
+}
+
+#[test]
+fn initial_supply_should_survive_mints_and_burns() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_ok!(Assets::inner_mint(&0, &2, 50));
+        assert_ok!(Assets::inner_burn(&0, &1, 80));
+        assert_eq!(Assets::total_supply(&0), 70);
+        assert_eq!(Assets::initial_supply(&0), 100);
+    });
+}
+
+#[test]
+fn min_precision_should_enforce_lot_size() {
+    new_test_ext().execute_with(|| {
+        let unit = 1_000_000_000_000_000_000;
+        assert_ok!(Assets::issue(Origin::signed(1), 10 * unit, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_min_precision(Origin::signed(1), 0, Some(1)));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, unit));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, unit / 10 * 15));
+        assert_noop!(
+            Assets::transfer(Origin::signed(1), 0, 2, unit / 100),
+            Error::<Test>::InvalidPrecision
+        );
+
+        assert_ok!(Assets::set_min_precision(Origin::signed(1), 0, Some(0)));
+        assert_noop!(
+            Assets::transfer(Origin::signed(1), 0, 2, unit / 10 * 15),
+            Error::<Test>::InvalidPrecision
+        );
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 2 * unit));
+
+        assert_ok!(Assets::set_min_precision(Origin::signed(1), 0, None));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 1));
+    });
+}
+
+#[test]
+fn min_precision_should_not_exceed_decimals() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_noop!(
+            Assets::set_min_precision(Origin::signed(1), 0, Some(TEST_ASSET_DECIMALS + 1)),
+            Error::<Test>::InvalidPrecision
+        );
+    });
+}
+
+#[test]
+fn allowances_sufficient_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 5, 50));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 5, 50));
+
+        assert_eq!(
+            Assets::allowances_sufficient(&0, &5, vec![(1, 50), (1, 51), (2, 10), (2, 11), (3, 1)]),
+            vec![true, false, true, false, false]
+        );
+    });
+}
+
+#[test]
+fn freeze_asset_should_block_all_transfers() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+
+        assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+        assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::AssetFrozen);
+        assert_noop!(Assets::transfer(Origin::signed(2), 0, 1, 10), Error::<Test>::AssetFrozen);
+
+        assert_ok!(Assets::thaw_asset(Origin::signed(1), 0));
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 1, 10));
+    });
+}
+
+#[test]
+fn renounce_freeze_authority_should_disable_freezing() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::freeze(Origin::signed(1), 0, 3));
+
+        assert_ok!(Assets::renounce_freeze_authority(Origin::signed(1), 0));
+        assert!(Assets::is_freeze_renounced(&0));
+        assert_noop!(Assets::freeze(Origin::signed(1), 0, 2), Error::<Test>::FreezeRenounced);
+        assert_noop!(Assets::thaw(Origin::signed(1), 0, 3), Error::<Test>::FreezeRenounced);
+        assert_noop!(
+            Assets::freeze_many(Origin::signed(1), 0, vec![2]),
+            Error::<Test>::FreezeRenounced
+        );
+        assert_noop!(Assets::freeze_asset(Origin::signed(1), 0), Error::<Test>::FreezeRenounced);
+        assert_noop!(Assets::thaw_asset(Origin::signed(1), 0), Error::<Test>::FreezeRenounced);
+    });
+}
+
+#[test]
+fn renounce_freeze_authority_should_not_work_while_asset_frozen() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+
+        assert_noop!(
+            Assets::renounce_freeze_authority(Origin::signed(1), 0),
+            Error::<Test>::AssetFrozen
+        );
+        assert_noop!(
+            Assets::renounce_freeze_authority(Origin::signed(2), 0),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn balances_root_should_be_deterministic() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Assets::balances_root(&0), Default::default());
+
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 30));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+
+        let leaf = |who: u64, balance: u64| BlakeTwo256::hash_of(&(0u8, who, balance));
+        let node = |left, right| BlakeTwo256::hash_of(&(1u8, left, right));
+        let expected = node(node(leaf(1, 60), leaf(2, 10)), leaf(3, 30));
+
+        assert_eq!(Assets::balances_root(&0), expected);
+        assert_eq!(Assets::balances_root(&0), expected);
+        assert_eq!(Assets::balances_root(&1), leaf(1, 100));
+    });
+}
+
+#[test]
+fn balances_root_should_change_with_balances() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        let before = Assets::balances_root(&0);
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 1));
+        assert_ne!(Assets::balances_root(&0), before);
+    });
+}
+
+#[test]
+fn redeem_should_pay_from_reserve() {
+    new_test_ext().execute_with(|| {
+        let _ = NativeBalances::deposit_creating(&1, 1_000);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+        assert_ok!(Assets::set_redemption(Origin::signed(1), 0, 3));
+        assert_eq!(Assets::redemption(0), Some((3, 1)));
+
+        assert_ok!(Assets::redeem(Origin::signed(2), 0, 40));
+        assert_eq!(Assets::balance_of(&0, &2), 10);
+        assert_eq!(Assets::total_supply(&0), 60);
+        assert_eq!(NativeBalances::free_balance(&2), 120);
+        assert_eq!(NativeBalances::free_balance(&1), 880);
+    });
+}
+
+#[test]
+fn redeem_should_not_work_with_insufficient_reserve() {
+    new_test_ext().execute_with(|| {
+        let _ = NativeBalances::deposit_creating(&1, 100);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+
+        assert_noop!(Assets::redeem(Origin::signed(2), 0, 10), Error::<Test>::RedemptionDisabled);
+
+        assert_ok!(Assets::set_redemption(Origin::signed(1), 0, 3));
+        assert_noop!(Assets::redeem(Origin::signed(2), 0, 40), Error::<Test>::ReserveInsufficient);
+        assert_noop!(Assets::redeem(Origin::signed(3), 0, 10), Error::<Test>::BalanceLow);
+    });
+}
+
+#[test]
+fn redeem_should_not_work_while_frozen() {
+    new_test_ext().execute_with(|| {
+        let _ = NativeBalances::deposit_creating(&1, 1_000);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+        assert_ok!(Assets::set_redemption(Origin::signed(1), 0, 3));
+
+        assert_ok!(Assets::freeze(Origin::signed(1), 0, 2));
+        assert_noop!(Assets::redeem(Origin::signed(2), 0, 10), Error::<Test>::AccountFrozen);
+        assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+        assert_noop!(Assets::redeem(Origin::signed(1), 0, 10), Error::<Test>::AssetFrozen);
+    });
+}
+
+#[test]
+fn transaction_fee_should_be_charged_and_refunded_in_fee_asset() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+        assert_ok!(Assets::set_fee_asset(Origin::root(), 0, 2));
+
+        let withdrawn = Assets::withdraw_fee(&2, 10).unwrap();
+        assert_eq!(withdrawn, Some((0, 20)));
+        assert_eq!(Assets::balance_of(&0, &2), 30);
+
+        assert_ok!(Assets::correct_and_deposit_fee(&2, 6, withdrawn));
+        assert_eq!(Assets::balance_of(&0, &2), 38);
+        assert_eq!(Assets::balance_of(&0, &1), 62);
+        assert_eq!(Assets::total_supply(&0), 100);
+    });
+}
+
+#[test]
+fn withdraw_fee_should_not_work_with_low_balance() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert!(Assets::withdraw_fee(&1, 10).is_err());
+
+        assert_ok!(Assets::set_fee_asset(Origin::root(), 0, 2));
+        assert!(Assets::withdraw_fee(&2, 10).is_err());
+        assert!(Assets::withdraw_fee(&1, 51).is_err());
+        assert_eq!(Assets::withdraw_fee(&1, 0), Ok(None));
+        assert_eq!(Assets::balance_of(&0, &1), 100);
+    });
+}
+
+#[test]
+fn withdraw_fee_should_not_work_while_asset_frozen() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_fee_asset(Origin::root(), 0, 2));
+        assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+
+        assert!(Assets::withdraw_fee(&1, 10).is_err());
+        assert_eq!(Assets::balance_of(&0, &1), 100);
+    });
+}
+
+#[test]
+fn set_fee_asset_should_only_clear_its_own_asset() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_fee_asset(Origin::root(), 0, 2));
+
+        assert_ok!(Assets::set_fee_asset(Origin::root(), 1, 0));
+        assert_eq!(Assets::fee_asset(), Some((0, 2)));
+        assert_ok!(Assets::set_fee_asset(Origin::root(), 0, 0));
+        assert_eq!(Assets::fee_asset(), None);
+    });
+}
+
+#[test]
+fn distribute_should_pay_holders_pro_rata() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 20));
+        assert_ok!(Assets::issue(Origin::signed(1), 1000, TEST_ASSET_INFO));
+
+        // Four balance entries to snapshot and three holders to pay, two steps per call.
+        assert_ok!(Assets::distribute(Origin::signed(1), 0, 1, 101, 2));
+        assert!(Assets::distribution(0).map_or(false, |d| d.snapshot_cursor.is_some()));
+        for _ in 0..3 {
+            assert_ok!(Assets::distribute(Origin::signed(1), 0, 1, 101, 2));
+        }
+        assert_eq!(Assets::distribution(0), None);
+
+        assert_eq!(Assets::balance_of(&1, &1), 1000 - 101 + 50 + 1);
+        assert_eq!(Assets::balance_of(&1, &2), 30);
+        assert_eq!(Assets::balance_of(&1, &3), 20);
+        assert_eq!(Assets::total_supply(&1), 1000);
+    });
+}
+
+#[test]
+fn distribute_should_pay_shares_from_start_of_distribution() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 20));
+        assert_ok!(Assets::issue(Origin::signed(1), 1000, TEST_ASSET_INFO));
+
+        assert_ok!(Assets::distribute(Origin::signed(1), 0, 1, 100, 1));
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 30));
+        assert_ok!(Assets::transfer(Origin::signed(3), 0, 2, 50));
+        assert_noop!(Assets::rebase(Origin::signed(1), 0, 2, 1), Error::<Test>::DistributionInProgress);
+        while Assets::distribution(0).is_some() {
+            assert_ok!(Assets::distribute(Origin::signed(1), 0, 1, 100, 4));
+        }
+
+        assert_eq!(Assets::balance_of(&1, &1), 1000 - 100 + 50);
+        assert_eq!(Assets::balance_of(&1, &2), 30);
+        assert_eq!(Assets::balance_of(&1, &3), 20);
+    });
+}
+
+#[test]
+fn distribute_should_not_work_with_other_arguments_in_progress() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+        assert_ok!(Assets::issue(Origin::signed(1), 1000, TEST_ASSET_INFO));
+
+        assert_noop!(Assets::distribute(Origin::signed(2), 0, 1, 100, 1), Error::<Test>::NotOwner);
+        assert_noop!(Assets::distribute(Origin::signed(1), 0, 1, 100, 5), Error::<Test>::BatchTooLarge);
+        assert_noop!(Assets::distribute(Origin::signed(1), 0, 1, 2000, 1), Error::<Test>::BalanceLow);
+
+        assert_ok!(Assets::distribute(Origin::signed(1), 0, 1, 100, 1));
+        assert_noop!(
+            Assets::distribute(Origin::signed(1), 0, 1, 50, 1),
+            Error::<Test>::DistributionInProgress
+        );
+    });
+}
+
+#[test]
+fn recent_transfers_should_keep_latest_transfers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        assert_ok!(Assets::issue(Origin::signed(1), 1000, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 1));
+        assert_eq!(Assets::recent_transfers(0), vec![(1, 2, 1, 3)]);
+
+        for amount in 2..=20 {
+            assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, amount));
+        }
+
+        let transfers = Assets::recent_transfers(0);
+        assert_eq!(transfers.len(), 16);
+        assert_eq!(transfers.first(), Some(&(1, 2, 5, 3)));
+        assert_eq!(transfers.last(), Some(&(1, 2, 20, 3)));
+    });
+}
+
+#[test]
+fn large_transfer_should_need_confirmation() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_large_transfer_confirmation(Origin::signed(1), 0, Some(50), 10));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 40));
+        assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 51), Error::<Test>::ConfirmationRequired);
+        assert_eq!(Assets::simulate_transfer(&0, &1, &2, 51), Err(Error::<Test>::ConfirmationRequired));
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 1, 40));
+
+        assert_ok!(Assets::initiate_transfer(Origin::signed(1), 0, 2, 60));
+        assert_eq!(Assets::balance_of(&0, &1), 40);
+        assert_noop!(Assets::initiate_transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::TransferPending);
+
+        System::set_block_number(10);
+        assert_ok!(Assets::confirm_transfer(Origin::signed(1), 0));
+        assert_eq!(Assets::balance_of(&0, &2), 60);
+        assert_eq!(Assets::pending_transfer((0, 1)), None);
+        assert_noop!(Assets::confirm_transfer(Origin::signed(1), 0), Error::<Test>::NoPendingTransfer);
+    });
+}
+
+#[test]
+fn large_transfer_should_need_confirmation_for_reserved_tokens() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_large_transfer_confirmation(Origin::signed(1), 0, Some(50), 10));
+
+        assert_noop!(
+            Assets::approve_and_reserve(Origin::signed(1), 0, 1, 60),
+            Error::<Test>::ConfirmationRequired
+        );
+        assert_ok!(Assets::approve_and_reserve(Origin::signed(1), 0, 1, 50));
+        assert_ok!(Assets::approve_and_reserve(Origin::signed(1), 0, 1, 50));
+        assert_noop!(
+            Assets::release_reserved(Origin::signed(1), 0, 1, 2, 100),
+            Error::<Test>::ConfirmationRequired
+        );
+        assert_ok!(Assets::release_reserved(Origin::signed(1), 0, 1, 1, 100));
+        assert_eq!(Assets::balance_of(&0, &1), 100);
+    });
+}
+
+#[test]
+fn pending_transfer_should_expire() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_large_transfer_confirmation(Origin::signed(1), 0, Some(50), 10));
+        assert_ok!(Assets::initiate_transfer(Origin::signed(1), 0, 2, 60));
+
+        System::set_block_number(11);
+        assert_ok!(Assets::confirm_transfer(Origin::signed(1), 0));
+        assert_eq!(Assets::balance_of(&0, &1), 100);
+        assert_eq!(Assets::balance_of(&0, &2), 0);
+        assert_eq!(Assets::pending_transfer((0, 1)), None);
+    });
+}
+
+#[test]
+fn pending_transfer_should_be_cancellable() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_noop!(
+            Assets::initiate_transfer(Origin::signed(1), 0, 2, 60),
+            Error::<Test>::ConfirmationNotRequired
+        );
+        assert_ok!(Assets::set_large_transfer_confirmation(Origin::signed(1), 0, Some(50), 10));
+        assert_ok!(Assets::initiate_transfer(Origin::signed(1), 0, 2, 60));
+
+        assert_ok!(Assets::cancel_transfer(Origin::signed(1), 0));
+        assert_eq!(Assets::balance_of(&0, &1), 100);
+        assert_eq!(Assets::balance_of(&0, &2), 0);
+        assert_noop!(Assets::cancel_transfer(Origin::signed(1), 0), Error::<Test>::NoPendingTransfer);
+    });
+}
+
+#[test]
+fn asset_id_ever_used_should_work() {
+    new_test_ext().execute_with(|| {
+        assert!(!Assets::asset_id_ever_used(&0));
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert!(Assets::asset_id_ever_used(&0));
+        assert!(!Assets::asset_id_ever_used(&1));
+    });
+}
+
+#[test]
+fn batch_permit_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let permits = vec![
+            (1, 3, 10, 0, TestSignature(1, (*b"permit", 0u32, 3u64, 10u64, 0u32).encode())),
+            (2, 3, 20, 0, TestSignature(2, (*b"permit", 0u32, 3u64, 20u64, 0u32).encode())),
+            (1, 4, 5, 1, TestSignature(1, (*b"permit", 0u32, 4u64, 5u64, 1u32).encode())),
+        ];
+
+        assert_ok!(Assets::batch_permit(Origin::signed(5), 0, permits));
+        assert_eq!(Assets::allowances(&0, &1, &3), 10);
+        assert_eq!(Assets::allowances(&0, &2, &3), 20);
+        assert_eq!(Assets::allowances(&0, &1, &4), 5);
+        assert_eq!(Assets::permit_nonce((0, 1)), 2);
+        assert_eq!(Assets::permit_nonce((0, 2)), 1);
+    });
+}
+
+#[test]
+fn batch_permit_should_not_apply_any_with_invalid_entry() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let valid = (1, 3, 10, 0, TestSignature(1, (*b"permit", 0u32, 3u64, 10u64, 0u32).encode()));
+
+        let forged = (2, 3, 20, 0, TestSignature(1, (*b"permit", 0u32, 3u64, 20u64, 0u32).encode()));
+        assert_noop!(
+            Assets::batch_permit(Origin::signed(5), 0, vec![valid.clone(), forged]),
+            Error::<Test>::BadSignature
+        );
+
+        // A transfer signature must not authorise an approval.
+        let transfer_signature = (2, 3, 20, 0, TestSignature(2, (0u32, 3u64, 20u64, 0u32).encode()));
+        assert_noop!(
+            Assets::batch_permit(Origin::signed(5), 0, vec![valid.clone(), transfer_signature]),
+            Error::<Test>::BadSignature
+        );
+
+        assert_noop!(
+            Assets::batch_permit(Origin::signed(5), 0, vec![valid.clone(), valid]),
+            Error::<Test>::BadNonce
+        );
+    });
+}
+
+#[test]
+fn fee_config_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Assets::fee_config(&0), None);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_eq!(Assets::fee_config(&0), Some((0, 0, 1)));
+
+        assert_ok!(Assets::set_transfer_fee(Origin::signed(1), 0, 250));
+        assert_ok!(Assets::set_fee_collector(Origin::signed(1), 0, 7));
+        assert_eq!(Assets::fee_config(&0), Some((250, 0, 7)));
+    });
+}
+
+#[test]
+fn max_allowance_should_cap_approvals() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_noop!(Assets::set_max_allowance(Origin::signed(2), 0, Some(10)), Error::<Test>::NotOwner);
+        assert_ok!(Assets::set_max_allowance(Origin::signed(1), 0, Some(10)));
+
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 10));
+        assert_eq!(Assets::allowances(&0, &1, &2), 10);
+        assert_noop!(Assets::approve(Origin::signed(1), 0, 2, 11), Error::<Test>::AllowanceTooLarge);
+
+        assert_ok!(Assets::set_max_allowance(Origin::signed(1), 0, None));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 11));
+    });
+}
+
+#[test]
+fn transfer_entire_asset_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 40));
+        assert_noop!(Assets::transfer_entire_asset(Origin::signed(3), 0, 2), Error::<Test>::NotOwner);
+
+        assert_ok!(Assets::transfer_entire_asset(Origin::signed(1), 0, 2));
+        assert_eq!(Assets::balance_of(&0, &1), 0);
+        assert_eq!(Assets::balance_of(&0, &2), 60);
+        assert_eq!(Assets::balance_of(&0, &3), 40);
+        assert_eq!(Assets::owner(0), Some(2));
+
+        let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+        assert!(events.contains(&TestEvent::erc20(RawEvent::Transferred(0, 1, 2, 60))));
+        assert_eq!(events.last(), Some(&TestEvent::erc20(RawEvent::OwnershipChanged(0, 1, 2))));
+    });
+}
+
+#[test]
+fn transfer_from_should_not_leave_owner_below_min_balance() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_min_balance(Origin::signed(1), 0, 10));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 200));
+
+        assert_noop!(Assets::transfer_from(Origin::signed(2), 0, 1, 3, 95), Error::<Test>::BalanceTooLow);
+        assert_noop!(Assets::transfer_from(Origin::signed(2), 0, 1, 3, 100), Error::<Test>::BalanceTooLow);
+        assert_noop!(Assets::transfer_from(Origin::signed(2), 0, 1, 3, 101), Error::<Test>::BalanceLow);
+        assert!(Assets::simulate_transfer_from(&0, &1, &2, &3, 95).is_err());
+
+        assert_ok!(Assets::transfer_from(Origin::signed(2), 0, 1, 3, 90));
+        assert_eq!(Assets::balance_of(&0, &1), 10);
+    });
+}
+
+#[test]
+fn emergency_withdraw_should_work_while_asset_frozen() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 40));
+        assert_ok!(Assets::set_recovery(Origin::signed(2), 0, 5));
+        assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+
+        assert_noop!(Assets::transfer(Origin::signed(2), 0, 5, 40), Error::<Test>::AssetFrozen);
+        assert_noop!(Assets::set_recovery(Origin::signed(2), 0, 6), Error::<Test>::AssetFrozen);
+        assert_ok!(Assets::emergency_withdraw(Origin::signed(2), 0, 5));
+        assert_eq!(Assets::balance_of(&0, &2), 0);
+        assert_eq!(Assets::balance_of(&0, &5), 40);
+    });
+}
+
+#[test]
+fn emergency_withdraw_should_not_work_while_asset_unfrozen() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 40));
+        assert_ok!(Assets::set_recovery(Origin::signed(2), 0, 5));
+
+        assert_noop!(Assets::emergency_withdraw(Origin::signed(2), 0, 5), Error::<Test>::AssetNotFrozen);
+
+        assert_ok!(Assets::set_wash_trade_protection(Origin::signed(1), 0, true));
+        assert_ok!(Assets::set_related_accounts(Origin::signed(1), 0, 2, 5, true));
+        assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+        assert_noop!(Assets::emergency_withdraw(Origin::signed(2), 0, 5), Error::<Test>::WashTradeBlocked);
+    });
+}
+
+#[test]
+fn emergency_withdraw_should_not_work_to_unregistered_address() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+        assert_noop!(Assets::emergency_withdraw(Origin::signed(1), 0, 5), Error::<Test>::NotRecoveryAddress);
+
+        assert_ok!(Assets::thaw_asset(Origin::signed(1), 0));
+        assert_ok!(Assets::set_recovery(Origin::signed(1), 0, 5));
+        assert_noop!(Assets::emergency_withdraw(Origin::signed(1), 0, 6), Error::<Test>::NotRecoveryAddress);
+    });
+}
+
+#[test]
+fn balance_diff_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+
+        assert_eq!(Assets::balance_diff(&0, &1, &2), 40);
+        assert_eq!(Assets::balance_diff(&0, &2, &1), -40);
+        assert_eq!(Assets::balance_diff(&0, &1, &1), 0);
+        assert_eq!(Assets::balance_diff(&0, &3, &4), 0);
+    });
+}
+
+#[test]
+fn transfers_should_not_work_before_trading_start_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_trading_start_block(Origin::signed(1), 0, 5));
+
+        assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::TradingNotStarted);
+        assert_ok!(Assets::inner_mint(&0, &2, 10));
+
+        System::set_block_number(5);
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 10));
+        assert_eq!(Assets::balance_of(&0, &3), 10);
+    });
+}
+
+#[test]
+fn reserve_allowance_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 50));
+
+        assert_ok!(Assets::reserve_allowance(Origin::signed(2), 0, 1, 30));
+        assert_eq!(Assets::balance_of(&0, &1), 70);
+        assert_eq!(Assets::reserved((0, 1, 2)), 30);
+        assert_eq!(Assets::allowances(&0, &1, &2), 20);
+        assert_noop!(Assets::reserve_allowance(Origin::signed(2), 0, 1, 21), Error::<Test>::AllowanceLow);
+
+        assert_ok!(Assets::release_reserved(Origin::signed(2), 0, 1, 3, 20));
+        assert_eq!(Assets::balance_of(&0, &3), 20);
+        assert_ok!(Assets::release_reserved(Origin::signed(2), 0, 1, 1, 10));
+        assert_eq!(Assets::balance_of(&0, &1), 80);
+        assert_eq!(Assets::reserved((0, 1, 2)), 0);
+        assert_noop!(Assets::release_reserved(Origin::signed(2), 0, 1, 1, 1), Error::<Test>::ReserveLow);
+    });
+}
+
+#[test]
+fn release_reserved_should_check_target() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 3, 50));
+        assert_ok!(Assets::reserve_allowance(Origin::signed(3), 0, 2, 30));
+
+        assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+        assert_noop!(Assets::release_reserved(Origin::signed(3), 0, 2, 4, 10), Error::<Test>::AssetFrozen);
+        assert_ok!(Assets::thaw_asset(Origin::signed(1), 0));
+
+        assert_ok!(Assets::set_restrict_destinations(Origin::signed(1), 0, true));
+        assert_noop!(
+            Assets::release_reserved(Origin::signed(3), 0, 2, 4, 10),
+            Error::<Test>::DestinationNotAllowed
+        );
+        assert_ok!(Assets::release_reserved(Origin::signed(3), 0, 2, 2, 10));
+        assert_eq!(Assets::balance_of(&0, &2), 30);
+        assert_eq!(Assets::reserved((0, 2, 3)), 20);
+    });
+}
+
+#[test]
+fn pallet_limits_should_match_config() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            Assets::pallet_limits(),
+            PalletLimits {
+                max_batch_size: MaxBatchSize::get(),
+                max_recent_transfers: 16,
+                max_fee_bps: 10_000,
+            }
+        );
+    });
+}
+
+#[test]
+fn transfer_tagged_should_emit_tag() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_ok!(Assets::transfer_tagged(Origin::signed(1), 0, 2, 10, 42));
+        assert_eq!(Assets::balance_of(&0, &2), 10);
+        assert_eq!(
+            System::events().last().map(|record| record.event.clone()),
+            Some(TestEvent::erc20(RawEvent::TransferredTagged(0, 1, 2, 10, 42)))
+        );
+    });
+}
+
+#[test]
+fn concentration_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Assets::concentration(&0), 0);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_eq!(Assets::concentration(&0), 10_000);
+
+        for who in 2..=4 {
+            assert_ok!(Assets::transfer(Origin::signed(1), 0, who, 25));
+        }
+        assert_eq!(Assets::concentration(&0), 2_500);
+
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 25));
+        assert_eq!(Assets::concentration(&0), 5_000);
+    });
+}
+
+#[test]
+fn owner_preauthorize_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let grants = vec![(2, 3, 10), (2, 4, 20), (5, 3, 30)];
+        assert_noop!(
+            Assets::owner_preauthorize(Origin::signed(2), 0, grants.clone()),
+            BadOrigin
+        );
+        assert_noop!(Assets::owner_preauthorize(Origin::signed(1), 0, grants.clone()), BadOrigin);
+
+        assert_ok!(Assets::owner_preauthorize(Origin::root(), 0, grants));
+        assert_eq!(Assets::allowances(&0, &2, &3), 10);
+        assert_eq!(Assets::allowances(&0, &2, &4), 20);
+        assert_eq!(Assets::allowances(&0, &5, &3), 30);
+        assert_eq!(Assets::allowance_count(0), 3);
+
+        assert_ok!(Assets::owner_preauthorize(Origin::root(), 0, vec![(2, 3, 0)]));
+        assert_eq!(Assets::allowances(&0, &2, &3), 0);
+    });
+}
+
+#[test]
+fn is_sole_holder_should_work() {
+    new_test_ext().execute_with(|| {
+        assert!(!Assets::is_sole_holder(&0, &1));
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert!(Assets::is_sole_holder(&0, &1));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        assert!(!Assets::is_sole_holder(&0, &1));
+        assert!(!Assets::is_sole_holder(&0, &2));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 90));
+        assert!(Assets::is_sole_holder(&0, &2));
+    });
+}
+
+#[test]
+fn rescue_from_burn_address_should_restore_supply() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let burn_address: u64 = BurnAddress::get();
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, burn_address, 30));
+        assert_eq!(Assets::total_supply(&0), 70);
+
+        assert_noop!(Assets::rescue_from_burn_address(Origin::signed(1), 0, 1, 30), BadOrigin);
+        assert_noop!(
+            Assets::rescue_from_burn_address(Origin::root(), 0, 1, 31),
+            Error::<Test>::RescueExceedsBurned
+        );
+
+        assert_ok!(Assets::rescue_from_burn_address(Origin::root(), 0, 1, 30));
+        assert_eq!(Assets::balance_of(&0, &1), 100);
+        assert_eq!(Assets::total_supply(&0), 100);
+        assert_eq!(Assets::burned_to_address(0), 0);
+    });
+}
+
+#[test]
+fn holder_enumeration_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 30));
+
+        assert_eq!(Assets::top_holders(&0, 2), vec![(1, 40), (2, 30)]);
+        assert_eq!(Assets::holder_rank(&0, &3), Some(3));
+        assert_eq!(Assets::holder_rank(&0, &4), None);
+    });
+}
+
+#[test]
+fn holder_enumeration_should_be_suppressed_when_not_enumerable() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_noop!(Assets::set_enumerable(Origin::signed(2), 0, false), Error::<Test>::NotOwner);
+        assert_ok!(Assets::set_enumerable(Origin::signed(1), 0, false));
+
+        assert_eq!(Assets::top_holders(&0, 2), vec![]);
+        assert_eq!(Assets::holder_rank(&0, &1), None);
+        assert_eq!(Assets::balance_of(&0, &1), 100);
+    });
+}
+
+#[test]
+fn rebase_should_scale_balances_and_supply() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 40));
+
+        assert_ok!(Assets::rebase(Origin::signed(1), 0, 2, 1));
+        assert_eq!(Assets::total_supply(&0), 200);
+        assert_eq!(Assets::balance_of(&0, &1), 120);
+        assert_eq!(Assets::balance_of(&0, &2), 80);
+
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 30));
+        assert_eq!(Assets::balance_of(&0, &2), 50);
+        assert_eq!(Assets::balance_of(&0, &3), 30);
+
+        assert_ok!(Assets::rebase(Origin::signed(1), 0, 1, 10));
+        assert_eq!(Assets::total_supply(&0), 20);
+        assert_eq!(Assets::balance_of(&0, &1), 12);
+        assert_eq!(Assets::balance_of(&0, &2), 5);
+        assert_eq!(Assets::balance_of(&0, &3), 3);
+    });
+}
+
+#[test]
+fn transfers_should_move_one_raw_amount_after_rebase() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::rebase(Origin::signed(1), 0, 3, 1));
+
+        assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 1), Error::<Test>::AmountTooSmall);
+
+        // 4 tokens are one raw unit, which is worth 3 tokens on both sides.
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 4));
+        assert_eq!(Assets::raw_and_scaled_balance(&0, &1), (99, 297));
+        assert_eq!(Assets::raw_and_scaled_balance(&0, &2), (1, 3));
+        assert_eq!(Assets::total_supply(&0), 300);
+    });
+}
+
+#[test]
+fn rebase_should_not_work_with_invalid_ratio() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_noop!(Assets::rebase(Origin::signed(2), 0, 2, 1), Error::<Test>::NotOwner);
+        assert_noop!(Assets::rebase(Origin::signed(1), 0, 0, 1), Error::<Test>::InvalidRebase);
+        assert_noop!(Assets::rebase(Origin::signed(1), 0, 1, 0), Error::<Test>::InvalidRebase);
+
+        assert_ok!(Assets::set_max_supply(Origin::signed(1), 0, Some(150)));
+        assert_noop!(Assets::rebase(Origin::signed(1), 0, 2, 1), Error::<Test>::SupplyCapExceeded);
+    });
+}
+
+#[test]
+fn raw_and_scaled_balance_should_follow_rebases() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_eq!(Assets::raw_and_scaled_balance(&0, &1), (100, 100));
+
+        assert_ok!(Assets::rebase(Origin::signed(1), 0, 3, 1));
+        assert_ok!(Assets::rebase(Origin::signed(1), 0, 1, 2));
+        assert_eq!(Assets::raw_and_scaled_balance(&0, &1), (100, 150));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+        let (raw, scaled) = Assets::raw_and_scaled_balance(&0, &2);
+        assert_eq!((raw, scaled), (20, 30));
+        assert_eq!(scaled, Assets::balance_of(&0, &2));
+        assert_eq!(Assets::raw_and_scaled_balance(&0, &1), (80, 120));
+    });
+}
+
+#[test]
+fn destroy_should_call_hook() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_noop!(Assets::destroy(Origin::signed(1), 0), Error::<Test>::SupplyNotZero);
+
+        let burn_address: u64 = BurnAddress::get();
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, burn_address, 100));
+        assert_noop!(Assets::destroy(Origin::signed(2), 0), Error::<Test>::NotOwner);
+        assert_ok!(Assets::destroy(Origin::signed(1), 0));
+
+        assert_eq!(Assets::asset_info(&0), None);
+        assert_eq!(Assets::owner(0), None);
+        assert!(Assets::asset_id_ever_used(&0));
+        assert_eq!(DESTROYED.with(|destroyed| destroyed.borrow().clone()), vec![0]);
+    });
+}
+
+#[test]
+fn transfer_to_alias_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_noop!(
+            Assets::transfer_to_alias(Origin::signed(1), 0, b"bob".to_vec(), 10),
+            Error::<Test>::UnknownAlias
+        );
+
+        assert_ok!(Assets::register_alias(Origin::signed(2), b"bob".to_vec()));
+        assert_noop!(Assets::register_alias(Origin::signed(3), b"bob".to_vec()), Error::<Test>::AliasTaken);
+        assert_ok!(Assets::transfer_to_alias(Origin::signed(1), 0, b"bob".to_vec(), 10));
+        assert_eq!(Assets::balance_of(&0, &2), 10);
+
+        assert_noop!(Assets::unregister_alias(Origin::signed(3), b"bob".to_vec()), Error::<Test>::UnknownAlias);
+        assert_ok!(Assets::unregister_alias(Origin::signed(2), b"bob".to_vec()));
+        assert_noop!(
+            Assets::transfer_to_alias(Origin::signed(1), 0, b"bob".to_vec(), 10),
+            Error::<Test>::UnknownAlias
+        );
+    });
+}
+
+#[test]
+fn issue_fixed_should_freeze_minting() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue_fixed(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_eq!(Assets::total_supply(&0), 100);
+        assert!(Assets::is_mint_frozen(0));
+        assert_noop!(Assets::inner_mint(&0, &1, 1), Error::<Test>::MintingFrozen);
+
+        let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+        assert_eq!(
+            events,
+            vec![
+                TestEvent::erc20(RawEvent::Issued(0, 1, 100)),
+                TestEvent::erc20(RawEvent::MetadataSet(0, TEST_ASSET_INFO)),
+                TestEvent::erc20(RawEvent::MintingFrozen(0)),
+            ]
+        );
+    });
+}
+
+#[test]
+fn asset_solvency_should_reconcile() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_eq!(Assets::asset_solvency(&0), (100, 0, 100));
+
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 30));
+        assert_ok!(Assets::reserve_allowance(Origin::signed(2), 0, 1, 30));
+        assert_ok!(Assets::set_large_transfer_confirmation(Origin::signed(1), 0, Some(5), 10));
+        assert_ok!(Assets::initiate_transfer(Origin::signed(1), 0, 3, 20));
+
+        let (free, reserved, supply) = Assets::asset_solvency(&0);
+        assert_eq!((free, reserved, supply), (50, 50, 100));
+        assert_eq!(free + reserved, supply);
+    });
+}
+
+#[test]
+fn merge_accounts_should_move_balance_and_allowances() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 40));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 3, 10));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 4, 20));
+        assert_ok!(Assets::approve(Origin::signed(5), 0, 3, 5));
+        assert_ok!(Assets::approve(Origin::signed(6), 0, 3, 7));
+
+        assert_noop!(Assets::merge_accounts(Origin::signed(1), 0, 2, 5), Error::<Test>::NotOwner);
+        assert_noop!(Assets::merge_accounts(Origin::signed(2), 0, 2, 5), Error::<Test>::MergeNotAccepted);
+        assert_ok!(Assets::accept_merge(Origin::signed(5), 0, 2));
+        assert_ok!(Assets::merge_accounts(Origin::signed(2), 0, 2, 5));
+        assert_eq!(Assets::merge_acceptance((0, 5)), None);
+
+        assert_eq!(Assets::balance_of(&0, &2), 0);
+        assert_eq!(Assets::balance_of(&0, &5), 40);
+        assert_eq!(Assets::allowances(&0, &2, &3), 0);
+        assert_eq!(Assets::allowances(&0, &2, &4), 0);
+        assert_eq!(Assets::allowances(&0, &5, &3), 15);
+        assert_eq!(Assets::allowances(&0, &5, &4), 20);
+        assert_eq!(Assets::allowances(&0, &6, &3), 7);
+        assert_eq!(Assets::allowance_count(0), 3);
+    });
+}
+
+#[test]
+fn event_verbosity_should_control_transfer_events() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let last_event = || System::events().last().map(|record| record.event.clone());
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        assert_eq!(last_event(), Some(TestEvent::erc20(RawEvent::Transferred(0, 1, 2, 10))));
+
+        assert_ok!(Assets::set_event_verbosity(Origin::signed(1), 0, EventVerbosity::MinimalAmountOnly));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 20));
+        assert_eq!(last_event(), Some(TestEvent::erc20(RawEvent::TransferredAmount(0, 20))));
+
+        assert_ok!(Assets::set_event_verbosity(Origin::signed(1), 0, EventVerbosity::None));
+        let events = System::events().len();
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+        assert_eq!(System::events().len(), events);
+        assert_eq!(Assets::balance_of(&0, &2), 60);
+    });
+}
+
+#[test]
+fn wind_down_should_only_allow_burns() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_noop!(Assets::wind_down(Origin::signed(2), 0), Error::<Test>::NotOwner);
+        assert_ok!(Assets::wind_down(Origin::signed(1), 0));
+
+        assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::AssetWoundDown);
+        assert_noop!(Assets::inner_mint(&0, &1, 10), Error::<Test>::AssetWoundDown);
+
+        let burn_address: u64 = BurnAddress::get();
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, burn_address, 10));
+        assert_eq!(Assets::total_supply(&0), 90);
+    });
+}
+
+#[test]
+fn wind_down_should_burn_reserved_tokens_released_to_burn_address() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve_and_reserve(Origin::signed(1), 0, 2, 10));
+        assert_ok!(Assets::wind_down(Origin::signed(1), 0));
+
+        assert_noop!(
+            Assets::release_reserved(Origin::signed(2), 0, 1, 3, 10),
+            Error::<Test>::AssetWoundDown
+        );
+        let burn_address: u64 = BurnAddress::get();
+        assert_ok!(Assets::release_reserved(Origin::signed(2), 0, 1, burn_address, 10));
+        assert_eq!(Assets::balance_of(&0, &burn_address), 0);
+        assert_eq!(Assets::total_supply(&0), 90);
+        assert_eq!(Assets::burned_to_address(0), 10);
+        assert_eq!(Assets::asset_solvency(&0), (90, 0, 90));
+    });
+}
+
+#[test]
+fn pending_transfers_should_list_incoming_transfers() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+        assert_ok!(Assets::set_large_transfer_confirmation(Origin::signed(1), 0, Some(5), 10));
+
+        assert_ok!(Assets::initiate_transfer(Origin::signed(1), 0, 3, 20));
+        System::set_block_number(4);
+        assert_ok!(Assets::initiate_transfer(Origin::signed(2), 0, 3, 10));
+
+        let mut pending = Assets::pending_transfers(&0, &3);
+        pending.sort();
+        assert_eq!(pending, vec![(10, 14), (20, 10)]);
+        assert_eq!(Assets::pending_transfers(&0, &1), vec![]);
+    });
+}
+
+#[test]
+fn recompute_counts_should_repair_counters() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 10));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 10));
+        HolderCount::<Test>::insert(0, 7);
+        AllowanceCount::<Test>::insert(0, 0);
+
+        assert_noop!(Assets::recompute_counts(Origin::signed(1), 0, 2), BadOrigin);
+        assert_ok!(Assets::recompute_counts(Origin::root(), 0, 2));
+        assert_eq!(Assets::count_recomputation(0).map(|(holders, _, _)| holders), Some(2));
+        assert_eq!(Assets::holder_count(0), 7);
+
+        assert_ok!(Assets::recompute_counts(Origin::root(), 0, 3));
+        assert_eq!(Assets::count_recomputation(0), None);
+        assert_eq!(Assets::holder_count(0), 3);
+        assert_eq!(Assets::allowance_count(0), 1);
+    });
+}
+
+#[test]
+fn recompute_counts_should_keep_changes_during_recount() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 10));
+        HolderCount::<Test>::insert(0, 7);
+
+        assert_ok!(Assets::recompute_counts(Origin::root(), 0, 1));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 4, 10));
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 4, 10));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 10));
+        assert_ok!(Assets::recompute_counts(Origin::root(), 0, 1));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 3, 10));
+
+        assert_ok!(Assets::recompute_counts(Origin::root(), 0, 5));
+        assert_eq!(Assets::count_recomputation(0), None);
+        assert_eq!(Assets::holder_count(0), 3);
+        assert_eq!(Assets::allowance_count(0), 2);
+    });
+}
+
+#[test]
+fn display_decimals_should_not_affect_transfers() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_display_decimals(Origin::signed(1), 0, Some(2)));
+
+        assert_eq!(Assets::asset_config(&0).and_then(|config| config.display_decimals), Some(2));
+        assert_eq!(Assets::asset_info(&0).map(|info| info.decimals), Some(TEST_ASSET_INFO.decimals));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 1));
+        assert_eq!(Assets::balance_of(&0, &2), 1);
+        assert_eq!(Assets::balance_of(&0, &1), 99);
+    });
+}
+
+#[test]
+fn pay_should_transfer_in_preferred_asset() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::issue(Origin::signed(1), 1000, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_exchange_rate(Origin::signed(1), 1, 5));
+        assert_ok!(Assets::set_preferred_asset(Origin::signed(2), Some(1)));
+
+        assert_ok!(Assets::pay(Origin::signed(1), 2, 20));
+        assert_eq!(Assets::balance_of(&1, &2), 100);
+        assert_eq!(Assets::balance_of(&1, &1), 900);
+        assert_eq!(Assets::balance_of(&0, &2), 0);
+    });
+}
+
+#[test]
+fn pay_should_not_work_without_preference_or_rate() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_noop!(Assets::pay(Origin::signed(1), 2, 20), Error::<Test>::NoPreferredAsset);
+
+        assert_ok!(Assets::set_preferred_asset(Origin::signed(2), Some(0)));
+        assert_noop!(Assets::pay(Origin::signed(1), 2, 20), Error::<Test>::NoExchangeRate);
+
+        assert_ok!(Assets::set_exchange_rate(Origin::signed(1), 0, u64::max_value()));
+        assert_noop!(Assets::pay(Origin::signed(1), 2, 20), Error::<Test>::ConversionFailed);
+    });
+}
+
+#[test]
+fn asset_state_digest_should_change_with_state() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Assets::asset_state_digest(&0), Default::default());
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        let digest = Assets::asset_state_digest(&0);
+        assert_ne!(digest, Default::default());
+        assert_eq!(Assets::asset_state_digest(&0), digest);
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        let transferred = Assets::asset_state_digest(&0);
+        assert_ne!(transferred, digest);
+
+        assert_ok!(Assets::set_transfer_fee(Origin::signed(1), 0, 100));
+        assert_ne!(Assets::asset_state_digest(&0), transferred);
+
+        let mut last = Assets::asset_state_digest(&0);
+        let mut assert_digest_changed = || {
+            let digest = Assets::asset_state_digest(&0);
+            assert_ne!(digest, last);
+            last = digest;
+        };
+        assert_ok!(Assets::set_event_verbosity(Origin::signed(1), 0, EventVerbosity::None));
+        assert_digest_changed();
+        assert_ok!(Assets::set_enumerable(Origin::signed(1), 0, false));
+        assert_digest_changed();
+        assert_ok!(Assets::set_trading_start_block(Origin::signed(1), 0, 5));
+        assert_digest_changed();
+        assert_ok!(Assets::set_max_allowance(Origin::signed(1), 0, Some(50)));
+        assert_digest_changed();
+        assert_ok!(Assets::set_large_transfer_confirmation(Origin::signed(1), 0, Some(50), 10));
+        assert_digest_changed();
+        assert_ok!(Assets::set_wash_trade_protection(Origin::signed(1), 0, true));
+        assert_digest_changed();
+        assert_ok!(Assets::wind_down(Origin::signed(1), 0));
+        assert_digest_changed();
+    });
+}
+
+#[test]
+fn approve_and_reserve_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_noop!(Assets::approve_and_reserve(Origin::signed(1), 0, 2, 101), Error::<Test>::BalanceLow);
+
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 2, 15));
+        assert_ok!(Assets::approve_and_reserve(Origin::signed(1), 0, 2, 40));
+        assert_eq!(Assets::balance_of(&0, &1), 60);
+        assert_eq!(Assets::reserved((0, 1, 2)), 40);
+        assert_eq!(Assets::allowances(&0, &1, &2), 15);
+
+        let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+        assert!(events.contains(&TestEvent::erc20(RawEvent::Approval(0, 1, 2, 15))));
+        assert!(events.contains(&TestEvent::erc20(RawEvent::AllowanceReserved(0, 1, 2, 40))));
+
+        assert_ok!(Assets::release_reserved(Origin::signed(2), 0, 1, 2, 40));
+        assert_eq!(Assets::balance_of(&0, &2), 40);
+    });
+}
+
+#[test]
+fn volume_last_n_blocks_should_sum_window() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 5));
+        System::set_block_number(3);
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 20));
+        System::set_block_number(4);
+
+        assert_eq!(Assets::volume_last_n_blocks(&0, 0), 0);
+        assert_eq!(Assets::volume_last_n_blocks(&0, 1), 0);
+        assert_eq!(Assets::volume_last_n_blocks(&0, 2), 20);
+        assert_eq!(Assets::volume_last_n_blocks(&0, 4), 35);
+        assert_eq!(Assets::block_volumes(0), vec![(1, 15), (3, 20)]);
+    });
+}
+
+#[test]
+fn burn_from_batch_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 9, 50));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 9, 30));
+
+        assert_ok!(Assets::burn_from_batch(Origin::signed(9), 0, vec![(1, 20), (2, 30), (1, 10)]));
+        assert_eq!(Assets::balance_of(&0, &1), 40);
+        assert_eq!(Assets::balance_of(&0, &2), 0);
+        assert_eq!(Assets::allowances(&0, &1, &9), 20);
+        assert_eq!(Assets::allowances(&0, &2, &9), 0);
+        assert_eq!(Assets::total_supply(&0), 40);
+    });
+}
+
+#[test]
+fn burn_from_batch_should_not_burn_any_with_invalid_entry() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 9, 50));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 9, 10));
+
+        assert_noop!(
+            Assets::burn_from_batch(Origin::signed(9), 0, vec![(1, 20), (2, 20)]),
+            Error::<Test>::AllowanceLow
+        );
+        assert_noop!(
+            Assets::burn_from_batch(Origin::signed(9), 0, vec![(1, 30), (1, 30)]),
+            Error::<Test>::AllowanceLow
+        );
+        assert_noop!(
+            Assets::burn_from_batch(Origin::signed(9), 0, vec![(1, 1), (1, 1), (1, 1), (1, 1), (1, 1)]),
+            Error::<Test>::BatchTooLarge
+        );
+    });
+}
+
+#[test]
+fn minters_should_list_owner_and_added_minters() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_eq!(Assets::minters(&0), vec![1]);
+
+        assert_ok!(Assets::add_minter(Origin::signed(1), 0, 2));
+        assert_ok!(Assets::add_minter(Origin::signed(1), 0, 3));
+        assert_ok!(Assets::add_minter(Origin::signed(1), 0, 1));
+        let mut minters = Assets::minters(&0);
+        assert_eq!(minters.remove(0), 1);
+        minters.sort();
+        assert_eq!(minters, vec![2, 3]);
+
+        assert_ok!(Assets::remove_minter(Origin::signed(1), 0, 2));
+        assert_eq!(Assets::minters(&0), vec![1, 3]);
+        assert!(Assets::minters(&1).is_empty());
+    });
+}
+
+#[test]
+fn transfer_above_floor_should_keep_floor() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_ok!(Assets::transfer_above_floor(Origin::signed(1), 0, 2, 30));
+        assert_eq!(Assets::balance_of(&0, &1), 30);
+        assert_eq!(Assets::balance_of(&0, &2), 70);
+    });
+}
+
+#[test]
+fn transfer_above_floor_should_fail_at_or_below_floor() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_noop!(
+            Assets::transfer_above_floor(Origin::signed(1), 0, 2, 100),
+            Error::<Test>::BalanceTooLow
+        );
+        assert_noop!(
+            Assets::transfer_above_floor(Origin::signed(1), 0, 2, 101),
+            Error::<Test>::BalanceTooLow
+        );
+    });
+}
+
+#[test]
+fn rebase_should_keep_supply_covering_balances() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 1));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 1));
+
+        assert_ok!(Assets::rebase(Origin::signed(1), 0, 1, 3));
+        assert_eq!(Assets::balance_of(&0, &2), 0);
+        assert_eq!(Assets::balance_of(&0, &3), 0);
+        // The 1/3 of a token lost on each of the other holders goes to the owner.
+        assert_eq!(Assets::balance_of(&0, &1), 33);
+        assert_eq!(Assets::total_supply(&0), 33);
+
+        for (numerator, denominator) in vec![(7, 3), (5, 11), (13, 7)] {
+            assert_ok!(Assets::rebase(Origin::signed(1), 0, numerator, denominator));
+            let (free, reserved, supply) = Assets::asset_solvency(&0);
+            assert!(free + reserved <= supply);
+            assert!(supply - (free + reserved) < Assets::holder_count(&0) as u64);
+        }
+    });
+}
+
+#[test]
+fn supply_should_cover_balances_after_writes_under_uneven_factor() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 1000, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 333));
+        assert_ok!(Assets::rebase(Origin::signed(1), 0, 7, 3));
+        let assert_solvent = || {
+            let (free, reserved, supply) = Assets::asset_solvency(&0);
+            assert!(free + reserved <= supply);
+            assert!(supply - (free + reserved) < Assets::holder_count(&0) as u64);
+        };
+        assert_solvent();
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 101));
+        assert_solvent();
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 55));
+        assert_solvent();
+        assert_ok!(Assets::inner_mint(&0, &4, 77));
+        assert_solvent();
+        assert_ok!(Assets::inner_burn(&0, &3, 13));
+        assert_solvent();
+        let balance = Assets::balance_of(&0, &2);
+        assert_ok!(Assets::transfer(Origin::signed(2), 0, 5, balance));
+        assert_eq!(Assets::balance_of(&0, &2), 0);
+        assert_solvent();
+    });
+}
+
+#[test]
+fn rebase_should_not_scale_reserved_tokens() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve_and_reserve(Origin::signed(1), 0, 2, 10));
+
+        assert_ok!(Assets::rebase(Origin::signed(1), 0, 2, 3));
+        assert_eq!(Assets::balance_of(&0, &1), 60);
+        assert_eq!(Assets::asset_solvency(&0), (60, 10, 70));
+    });
+}
+
+#[test]
+fn transfer_with_return_approval_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_ok!(Assets::transfer_with_return_approval(Origin::signed(1), 0, 2, 40));
+        assert_eq!(Assets::balance_of(&0, &1), 60);
+        assert_eq!(Assets::balance_of(&0, &2), 40);
+        assert_eq!(Assets::allowances(&0, &2, &1), 40);
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == TestEvent::erc20(RawEvent::Approval(0, 2, 1, 40))));
+
+        assert_ok!(Assets::transfer_from(Origin::signed(1), 0, 2, 1, 25));
+        assert_eq!(Assets::balance_of(&0, &1), 85);
+        assert_eq!(Assets::allowances(&0, &2, &1), 15);
+    });
+}
+
+#[test]
+fn transfer_with_return_approval_should_not_work_without_delegation() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_allow_delegation(Origin::signed(1), 0, false));
+
+        assert_noop!(
+            Assets::transfer_with_return_approval(Origin::signed(1), 0, 2, 40),
+            Error::<Test>::DelegationDisabled
+        );
+    });
+}
+
+#[test]
+fn granted_allowances_should_page_by_spender() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        for spender in vec![5, 3, 4, 2, 6] {
+            assert_ok!(Assets::approve(Origin::signed(1), 0, spender, spender * 10));
+        }
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 3, 7));
+
+        assert_eq!(Assets::granted_allowances(&0, &1, 0, 2), vec![(2, 20), (3, 30)]);
+        assert_eq!(Assets::granted_allowances(&0, &1, 2, 2), vec![(4, 40), (5, 50)]);
+        assert_eq!(Assets::granted_allowances(&0, &1, 4, 2), vec![(6, 60)]);
+        assert!(Assets::granted_allowances(&0, &1, 6, 2).is_empty());
+
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 3, 0));
+        assert_eq!(Assets::granted_allowances(&0, &1, 0, 2), vec![(2, 20), (4, 40)]);
+    });
+}
+
+#[test]
+fn launch_asset_should_configure_everything() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::launch_asset(
+            Origin::signed(1),
+            TEST_ASSET_INFO,
+            vec![(2, 30), (3, 50), (2, 10)],
+            100,
+            Some(9),
+            Some(500)
+        ));
+
+        assert_eq!(Assets::owner(0), Some(1));
+        assert_eq!(Assets::asset_info(&0), Some(TEST_ASSET_INFO));
+        assert_eq!(Assets::balance_of(&0, &1), 0);
+        assert_eq!(Assets::balance_of(&0, &2), 40);
+        assert_eq!(Assets::balance_of(&0, &3), 50);
+        assert_eq!(Assets::total_supply(&0), 90);
+        assert_eq!(Assets::initial_supply(0), 90);
+        assert_eq!(Assets::fee_config(&0), Some((100, 0, 9)));
+        assert_eq!(Assets::max_supply(0), Some(500));
+    });
+}
+
+#[test]
+fn launch_asset_should_validate_before_issuing() {
+    new_test_ext().execute_with(|| {
+        let launch = |distribution, fee_bps, max_supply| {
+            Assets::launch_asset(Origin::signed(1), TEST_ASSET_INFO, distribution, fee_bps, None, max_supply)
+        };
+
+        assert_noop!(launch(vec![(2, 30)], 100, Some(20)), Error::<Test>::SupplyCapExceeded);
+        assert_noop!(launch(vec![(2, 30)], 10_001, None), Error::<Test>::InvalidFee);
+        assert_noop!(launch(vec![(2, 0)], 100, None), Error::<Test>::AmountZero);
+        assert_eq!(Assets::next_asset_id(), 0);
+    });
+}
+
+#[test]
+fn estimate_batch_weight_should_scale_with_count() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Assets::estimate_batch_weight(0), 0);
+        assert_eq!(Assets::estimate_batch_weight(1), 1_000);
+        assert_eq!(Assets::estimate_batch_weight(4), 4 * Assets::estimate_batch_weight(1));
+
+        let transfer = Call::<Test>::transfer(0, 2, 10);
+        assert_eq!(transfer.get_dispatch_info().weight, Assets::estimate_batch_weight(1));
+        let airdrop = Call::<Test>::airdrop_equal(0, vec![2, 3, 4], 10);
+        assert_eq!(airdrop.get_dispatch_info().weight, Assets::estimate_batch_weight(3));
+    });
+}
+
+#[test]
+fn lock_forever_should_take_tokens_out_of_circulation() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_ok!(Assets::lock_forever(Origin::signed(1), 0, 30));
+        assert_eq!(Assets::balance_of(&0, &1), 70);
+        assert_eq!(Assets::permanently_locked(0), 30);
+        assert_eq!(Assets::total_supply(&0), 100);
+        assert_eq!(Assets::circulating_supply(&0), 70);
+        assert_eq!(Assets::asset_solvency(&0), (70, 30, 100));
+
+        assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 71), Error::<Test>::BalanceLow);
+        assert_noop!(Assets::lock_forever(Origin::signed(1), 0, 71), Error::<Test>::BalanceLow);
+        assert_noop!(Assets::destroy(Origin::signed(1), 0), Error::<Test>::SupplyNotZero);
+    });
+}
+
+#[test]
+fn supply_composition_should_add_up_to_supply() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let schedule = VestingSchedule { locked: 50, per_block: 10, starting_block: 10 };
+        assert_ok!(Assets::mint_vested(Origin::signed(1), 0, 2, schedule));
+        assert_ok!(Assets::approve_and_reserve(Origin::signed(1), 0, 3, 20));
+        assert_ok!(Assets::lock_forever(Origin::signed(1), 0, 30));
+
+        assert_eq!(Assets::supply_composition(&0), (50, 20, 50, 30));
+
+        System::set_block_number(12);
+        let (circulating, reserved, vesting_locked, permanently_locked) = Assets::supply_composition(&0);
+        assert_eq!((circulating, vesting_locked), (70, 30));
+        assert_eq!(circulating + reserved + vesting_locked + permanently_locked, Assets::total_supply(&0));
+    });
+}
+
+#[test]
+fn commit_reveal_transfer_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let salt = [7u8; 32];
+        let hash = BlakeTwo256::hash_of(&(0u32, 2u64, 40u64, salt));
+
+        assert_ok!(Assets::commit_transfer(Origin::signed(1), hash));
+        assert_noop!(Assets::reveal_transfer(Origin::signed(1), 0, 2, 40, salt), Error::<Test>::TooEarly);
+
+        System::set_block_number(3);
+        assert_ok!(Assets::reveal_transfer(Origin::signed(1), 0, 2, 40, salt));
+        assert_eq!(Assets::balance_of(&0, &2), 40);
+        assert_eq!(Assets::transfer_commitment((1, hash)), None);
+        assert_noop!(Assets::reveal_transfer(Origin::signed(1), 0, 2, 40, salt), Error::<Test>::BadReveal);
+    });
+}
+
+#[test]
+fn reveal_transfer_should_not_work_with_mismatched_reveal() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let salt = [7u8; 32];
+        let hash = BlakeTwo256::hash_of(&(0u32, 2u64, 40u64, salt));
+        assert_ok!(Assets::commit_transfer(Origin::signed(1), hash));
+        System::set_block_number(5);
+
+        assert_noop!(Assets::reveal_transfer(Origin::signed(1), 0, 2, 41, salt), Error::<Test>::BadReveal);
+        assert_noop!(Assets::reveal_transfer(Origin::signed(1), 0, 3, 40, salt), Error::<Test>::BadReveal);
+        assert_noop!(Assets::reveal_transfer(Origin::signed(2), 0, 2, 40, salt), Error::<Test>::BadReveal);
+    });
+}
+
+#[test]
+fn spendable_assets_should_follow_grants_and_revocations() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::issue(Origin::signed(2), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 9, 10));
+        assert_ok!(Assets::approve(Origin::signed(2), 1, 9, 20));
+        assert_ok!(Assets::approve(Origin::signed(3), 0, 9, 30));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 8, 40));
+
+        let mut spendable = Assets::spendable_assets(&9);
+        spendable.sort();
+        assert_eq!(spendable, vec![(0, 1, 10), (0, 3, 30), (1, 2, 20)]);
+
+        assert_ok!(Assets::approve(Origin::signed(3), 0, 9, 0));
+        assert_ok!(Assets::transfer_from(Origin::signed(9), 0, 1, 4, 10));
+        assert_eq!(Assets::spendable_assets(&9), vec![(1, 2, 20)]);
+        assert_eq!(Assets::spendable_assets(&8), vec![(0, 1, 40)]);
+    });
+}
+
+#[test]
+fn runtime_upgrade_should_index_existing_allowances() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve(Origin::signed(1), 0, 9, 10));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 9, 20));
+        SpenderAllowances::<Test>::remove_prefix(&9);
+        assert!(Assets::spendable_assets(&9).is_empty());
+
+        Assets::on_runtime_upgrade();
+        let mut spendable = Assets::spendable_assets(&9);
+        spendable.sort();
+        assert_eq!(spendable, vec![(0, 1, 10), (0, 2, 20)]);
+
+        SpenderAllowances::<Test>::remove_prefix(&9);
+        Assets::on_runtime_upgrade();
+        assert!(Assets::spendable_assets(&9).is_empty());
+    });
+}
+
+#[test]
+fn runtime_upgrade_should_index_granted_allowances() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 3, 10));
+        assert_ok!(Assets::approve(Origin::signed(2), 0, 4, 20));
+        OwnerAllowances::<Test>::remove_prefix((0, 2));
+
+        Assets::on_runtime_upgrade();
+        assert_ok!(Assets::accept_merge(Origin::signed(5), 0, 2));
+        assert_ok!(Assets::merge_accounts(Origin::signed(2), 0, 2, 5));
+        assert_eq!(Assets::allowances(&0, &5, &3), 10);
+        assert_eq!(Assets::allowances(&0, &5, &4), 20);
+        assert_eq!(Assets::allowance_count(0), 2);
+    });
+}
+
+#[test]
+fn runtime_upgrade_should_backfill_raw_supply() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 30));
+        assert_eq!(Assets::raw_supply(&0), 100);
+
+        Assets::on_runtime_upgrade();
+        assert_eq!(Assets::raw_supply(&0), 100);
+
+        RawSupply::<Test>::remove(0);
+        Assets::on_runtime_upgrade();
+        assert_eq!(Assets::raw_supply(&0), 0);
+    });
+}
+
+#[test]
+fn rebrand_should_change_owner_and_metadata() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let new_info = AssetInfo { name: *b"rebranded_token!", ..TEST_ASSET_INFO };
+
+        assert_ok!(Assets::rebrand(Origin::signed(1), 0, 2, new_info.clone()));
+        assert_eq!(Assets::owner(0), Some(2));
+        assert_eq!(Assets::asset_info(&0), Some(new_info.clone()));
+        let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+        assert!(events.contains(&TestEvent::erc20(RawEvent::OwnershipChanged(0, 1, 2))));
+        assert!(events.contains(&TestEvent::erc20(RawEvent::MetadataChanged(0, new_info))));
+    });
+}
+
+#[test]
+fn rebrand_should_not_change_decimals() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        let new_info = AssetInfo { decimals: TEST_ASSET_INFO.decimals + 1, ..TEST_ASSET_INFO };
+
+        assert_noop!(
+            Assets::rebrand(Origin::signed(1), 0, 2, new_info),
+            Error::<Test>::DecimalsImmutable
+        );
+        assert_noop!(Assets::rebrand(Origin::signed(2), 0, 2, TEST_ASSET_INFO), Error::<Test>::NotOwner);
+    });
+}
+
+#[test]
+fn quote_transfer_should_match_transfer() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 1000, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_transfer_fee(Origin::signed(1), 0, 250));
+        assert_eq!(Assets::quote_transfer(&0, 100), (100, 0, 0));
+
+        assert_ok!(Assets::set_fee_collector(Origin::signed(1), 0, 9));
+        assert_eq!(Assets::quote_transfer(&0, 100), (98, 2, 0));
+        assert_eq!(Assets::quote_transfer(&0, 39), (39, 0, 0));
+        assert_eq!(Assets::quote_transfer(&0, 0), (0, 0, 0));
+
+        let (net, fee, _) = Assets::quote_transfer(&0, 123);
+        assert_eq!((net, fee), (120, 3));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 123));
+        assert_eq!(Assets::balance_of(&0, &2), net);
+        assert_eq!(Assets::balance_of(&0, &9), fee);
+    });
+}
+
+#[test]
+fn airdrop_equal_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_ok!(Assets::airdrop_equal(Origin::signed(1), 0, vec![2, 3, 4, 5], 25));
+        assert_eq!(Assets::balance_of(&0, &1), 0);
+        for who in 2..=5 {
+            assert_eq!(Assets::balance_of(&0, &who), 25);
+        }
+    });
+}
+
+#[test]
+fn airdrop_equal_should_not_work_over_balance_or_bound() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_noop!(
+            Assets::airdrop_equal(Origin::signed(1), 0, vec![2, 3, 4], 34),
+            Error::<Test>::BalanceLow
+        );
+        assert_noop!(
+            Assets::airdrop_equal(Origin::signed(1), 0, vec![2, 3], u64::max_value()),
+            Error::<Test>::BalanceLow
+        );
+        assert_noop!(
+            Assets::airdrop_equal(Origin::signed(1), 0, vec![2, 3, 4, 5, 6], 1),
+            Error::<Test>::BatchTooLarge
+        );
+    });
+}
+
+#[test]
+fn airdrop_equal_should_not_work_with_sender_or_duplicate_recipients() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+
+        assert_noop!(
+            Assets::airdrop_equal(Origin::signed(1), 0, vec![2, 1], 10),
+            Error::<Test>::InvalidRecipient
+        );
+        assert_noop!(
+            Assets::airdrop_equal(Origin::signed(1), 0, vec![2, 3, 2], 10),
+            Error::<Test>::InvalidRecipient
+        );
+    });
+}
+
+#[test]
+fn wash_trade_protection_should_block_self_and_related_transfers() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::set_related_accounts(Origin::signed(1), 0, 1, 2, true));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+
+        assert_ok!(Assets::set_wash_trade_protection(Origin::signed(1), 0, true));
+        assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::WashTradeBlocked);
+        assert_noop!(Assets::transfer(Origin::signed(2), 0, 1, 10), Error::<Test>::WashTradeBlocked);
+        assert_noop!(Assets::transfer(Origin::signed(1), 0, 1, 10), Error::<Test>::WashTradeBlocked);
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 10));
+
+        assert_ok!(Assets::set_related_accounts(Origin::signed(1), 0, 2, 1, false));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        assert_eq!(Assets::balance_of(&0, &2), 20);
+    });
+}
+
+#[test]
+fn reset_volume_should_clear_windowed_volume() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+        System::set_block_number(2);
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 20));
+        assert_eq!(Assets::volume_last_n_blocks(&0, 5), 30);
+
+        assert_noop!(Assets::reset_volume(Origin::signed(1), 0), BadOrigin);
+        assert_ok!(Assets::reset_volume(Origin::root(), 0));
+        assert_eq!(Assets::volume_last_n_blocks(&0, 5), 0);
+        assert_eq!(System::events().last().unwrap().event, TestEvent::erc20(RawEvent::VolumeReset(0)));
+
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 5));
+        assert_eq!(Assets::volume_last_n_blocks(&0, 5), 5);
+    });
+}
+
+#[test]
+fn balance_conversion_should_be_lossless_or_fail() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Assets::to_native(&0, 1_000), Some(1_000));
+        assert_eq!(Assets::from_native(&0, u64::max_value()), Some(u64::max_value()));
+
+        assert_eq!(<() as BalanceConverter<u32, u64, u8>>::to_native(&0, 255), Some(255));
+        assert_eq!(<() as BalanceConverter<u32, u64, u8>>::to_native(&0, 256), None);
+        assert_eq!(<() as BalanceConverter<u32, u8, u128>>::from_native(&0, 1 << 8), None);
+    });
+}
+
+#[test]
+fn merge_accounts_should_not_grant_allowances_on_unwilling_account() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::issue(Origin::signed(1), 100, TEST_ASSET_INFO));
+        assert_ok!(Assets::approve(Origin::signed(7), 0, 8, 1_000));
+
+        assert_noop!(Assets::merge_accounts(Origin::signed(7), 0, 7, 1), Error::<Test>::MergeNotAccepted);
+        assert_ok!(Assets::accept_merge(Origin::signed(1), 0, 9));
+        assert_noop!(Assets::merge_accounts(Origin::signed(7), 0, 7, 1), Error::<Test>::MergeNotAccepted);
+        assert_eq!(Assets::allowances(&0, &1, &8), 0);
+
+        assert_ok!(Assets::accept_merge(Origin::signed(1), 0, 7));
+        assert_ok!(Assets::set_max_allowance(Origin::signed(1), 0, Some(500)));
+        assert_noop!(Assets::merge_accounts(Origin::signed(7), 0, 7, 1), Error::<Test>::AllowanceTooLarge);
+    });
+}